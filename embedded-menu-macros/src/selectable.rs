@@ -25,6 +25,23 @@ pub fn expand_next_fn(input: &DataEnum) -> syn::Result<TokenStream> {
     })
 }
 
+pub fn expand_previous_fn(input: &DataEnum) -> syn::Result<TokenStream> {
+    let variants = input.variants.iter().map(|variant| &variant.ident);
+
+    let branches = variants
+        .clone()
+        .zip(variants.cycle().skip(1))
+        .map(|(previous, current)| {
+            quote! { Self::#current => Self::#previous }
+        });
+
+    Ok(quote! {
+        *self = match self {
+            #(#branches),*
+        };
+    })
+}
+
 pub fn expand_name_fn(input: &DataEnum) -> syn::Result<TokenStream> {
     let variants = input
         .variants
@@ -60,6 +77,36 @@ pub fn expand_name_fn(input: &DataEnum) -> syn::Result<TokenStream> {
     })
 }
 
+/// Variants are stored as their position in the enum.
+pub fn expand_stored_value_fn(input: &DataEnum) -> syn::Result<TokenStream> {
+    let branches = input.variants.iter().enumerate().map(|(index, variant)| {
+        let ident = &variant.ident;
+        let index = index as u32;
+        quote! { Self::#ident => #index }
+    });
+
+    Ok(quote! {
+        match self {
+            #(#branches),*
+        }
+    })
+}
+
+pub fn expand_from_stored_value_fn(input: &DataEnum) -> syn::Result<TokenStream> {
+    let branches = input.variants.iter().enumerate().map(|(index, variant)| {
+        let ident = &variant.ident;
+        let index = index as u32;
+        quote! { #index => ::core::option::Option::Some(Self::#ident) }
+    });
+
+    Ok(quote! {
+        match stored {
+            #(#branches,)*
+            _ => ::core::option::Option::None,
+        }
+    })
+}
+
 pub fn expand_select_value(input: DeriveInput) -> syn::Result<TokenStream> {
     let Data::Enum(data) = &input.data else {
         unimplemented!("SelectValue can only be derived on enums");
@@ -68,7 +115,10 @@ pub fn expand_select_value(input: DeriveInput) -> syn::Result<TokenStream> {
     let enum_name = input.ident;
 
     let next_body = expand_next_fn(data)?;
+    let previous_body = expand_previous_fn(data)?;
     let name_body = expand_name_fn(data)?;
+    let stored_value_body = expand_stored_value_fn(data)?;
+    let from_stored_value_body = expand_from_stored_value_fn(data)?;
 
     Ok(quote! {
         impl embedded_menu::items::menu_item::SelectValue for #enum_name {
@@ -76,9 +126,21 @@ pub fn expand_select_value(input: DeriveInput) -> syn::Result<TokenStream> {
                 #next_body
             }
 
+            fn previous(&mut self) {
+                #previous_body
+            }
+
             fn marker(&self) -> &str {
                 #name_body
             }
+
+            fn stored_value(&self) -> ::core::option::Option<u32> {
+                ::core::option::Option::Some(#stored_value_body)
+            }
+
+            fn from_stored_value(stored: u32) -> ::core::option::Option<Self> {
+                #from_stored_value_body
+            }
         }
     })
 }
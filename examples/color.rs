@@ -48,10 +48,7 @@ fn main() -> Result<(), core::convert::Infallible> {
         "Color Menu",
         MenuStyle::new(ExampleTheme)
             .with_selection_indicator(RectangleIndicator)
-            .with_input_adapter(Simulator {
-                page_size: 5,
-                esc_value: (),
-            }),
+            .with_input_adapter(Simulator::new(5, ())),
     )
     .add_item("Foo", ">", |_| ())
     .add_item("Check this", false, |_| ())
@@ -47,11 +47,7 @@ fn main() -> Result<(), core::convert::Infallible> {
         "Color Menu",
         MenuStyle::new(ExampleTheme)
             .with_selection_indicator(AnimatedTriangle::new(160))
-            .with_input_adapter(SingleTouch {
-                ignore_time: 10,
-                debounce_time: 1,
-                max_time: 100,
-            }),
+            .with_input_adapter(SingleTouch::new(10, 1, 100)),
     )
     .add_item("Foo", ">", |_| ())
     .add_item("Check this", false, |_| ())
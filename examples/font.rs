@@ -25,10 +25,7 @@ fn main() -> Result<(), core::convert::Infallible> {
         MenuStyle::default()
             .with_font(&FONT_6X10)
             .with_title_font(&FONT_8X13_BOLD)
-            .with_input_adapter(Simulator {
-                page_size: 5,
-                esc_value: (),
-            }),
+            .with_input_adapter(Simulator::new(5, ())),
     )
     .add_item("Nav item", "»", |_| ()) // » is not part of the ASCII font
     .add_item("Checkbox", true, |_| ())
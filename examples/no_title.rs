@@ -19,10 +19,7 @@ pub enum TestEnum {
 fn main() -> Result<(), core::convert::Infallible> {
     let mut menu = Menu::with_style(
         "",
-        MenuStyle::default().with_input_adapter(Simulator {
-            page_size: 5,
-            esc_value: (),
-        }),
+        MenuStyle::default().with_input_adapter(Simulator::new(5, ())),
     )
     .add_item("Foo", ">", |_| ())
     .add_item("Check this 1", false, |_| ())
@@ -26,10 +26,7 @@ fn main() -> Result<(), core::convert::Infallible> {
 
     let mut menu = Menu::with_style(
         format!("Items: {}", items.len()),
-        MenuStyle::default().with_input_adapter(Simulator {
-            page_size: 5,
-            esc_value: (),
-        }),
+        MenuStyle::default().with_input_adapter(Simulator::new(5, ())),
     )
     .add_item("Foo", ">", |_| ())
     .add_menu_items(items)
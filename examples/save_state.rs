@@ -40,10 +40,7 @@ fn do_loop(
     item_count: usize,
 ) -> bool {
     let style = MenuStyle::new(BinaryColor::On)
-        .with_input_adapter(Simulator {
-            page_size: 5,
-            esc_value: MenuEvent::Quit,
-        })
+        .with_input_adapter(Simulator::new(5, MenuEvent::Quit))
         .with_animated_selection_indicator(10);
 
     let title = format!("{item_count} items");
@@ -51,14 +48,16 @@ fn do_loop(
     for _ in 0..60 {
         let mut items = (0..item_count)
             .map(|i| {
-                MenuItem::new("Changing", data.slice_data[i]).with_value_converter(match i {
-                    0 => |data| MenuEvent::SliceCheckbox(0, data),
-                    1 => |data| MenuEvent::SliceCheckbox(1, data),
-                    2 => |data| MenuEvent::SliceCheckbox(2, data),
-                    3 => |data| MenuEvent::SliceCheckbox(3, data),
-                    4 => |data| MenuEvent::SliceCheckbox(4, data),
-                    _ => panic!(),
-                })
+                MenuItem::new("Changing", data.slice_data[i])
+                    .with_value_converter(match i {
+                        0 => |data| MenuEvent::SliceCheckbox(0, data),
+                        1 => |data| MenuEvent::SliceCheckbox(1, data),
+                        2 => |data| MenuEvent::SliceCheckbox(2, data),
+                        3 => |data| MenuEvent::SliceCheckbox(3, data),
+                        4 => |data| MenuEvent::SliceCheckbox(4, data),
+                        _ => panic!(),
+                    })
+                    .with_id(i as u16)
             })
             .take(item_count)
             .collect::<Vec<_>>();
@@ -68,8 +67,13 @@ fn do_loop(
             .add_section_title("  Dynamic items")
             .add_menu_items(&mut items)
             .add_section_title("  Non-Dynamic")
-            .add_item("Check this too", data.select, MenuEvent::Select)
-            .build_with_state(*state);
+            .add_menu_item(
+                MenuItem::new("Check this too", data.select)
+                    .with_value_converter(MenuEvent::Select)
+                    .with_id(100),
+            )
+            // Keep the selected item selected, even if items were inserted or removed before it
+            .build_with_state_by_id(*state);
 
         let mut display = SimulatorDisplay::new(Size::new(128, 64));
 
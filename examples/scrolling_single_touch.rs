@@ -2,6 +2,7 @@
 //!
 //! Navigate using only the spacebar. Short(ish) press moves on to the next item, long press activates.
 //! Watch the animated selection indicator fill up. Long press is registered as the bar reaches full width.
+//! Double tap moves to the previous item, tapping once and then holding keeps scrolling down.
 
 use embedded_graphics::{prelude::Size, Drawable};
 use embedded_graphics_simulator::{
@@ -23,11 +24,11 @@ pub enum TestEnum {
 fn main() -> Result<(), core::convert::Infallible> {
     let style = MenuStyle::default()
         .with_selection_indicator(AnimatedTriangle::new(160))
-        .with_input_adapter(SingleTouch {
-            ignore_time: 10,
-            debounce_time: 1,
-            max_time: 100,
-        })
+        .with_input_adapter(
+            SingleTouch::new(10, 1, 100)
+                .with_double_tap(20)
+                .with_auto_advance(15),
+        )
         .with_animated_selection_indicator(10);
 
     let mut menu = Menu::with_style("Menu with even longer title", style)
@@ -17,10 +17,7 @@ pub enum TestEnum {
 
 fn main() -> Result<(), core::convert::Infallible> {
     let style = MenuStyle::default()
-        .with_input_adapter(Simulator {
-            page_size: 5,
-            esc_value: (),
-        })
+        .with_input_adapter(Simulator::new(5, ()))
         .with_animated_selection_indicator(10);
 
     let selects1 = [
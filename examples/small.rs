@@ -22,10 +22,7 @@ pub enum TestEnum {
 fn main() -> Result<(), core::convert::Infallible> {
     let mut menu = Menu::with_style(
         "Menu",
-        MenuStyle::default().with_input_adapter(Simulator {
-            page_size: 5,
-            esc_value: (),
-        }),
+        MenuStyle::default().with_input_adapter(Simulator::new(5, ())),
     )
     .add_item("Foo", ">", |_| ())
     .add_item("Check this", false, |_| ())
@@ -1,63 +1,144 @@
 use crate::{
-    collection::{MenuItemCollection, MenuItems},
+    collection::{ItemProvider, MenuItemCollection, MenuItems, VirtualItems},
+    event::MenuEvent,
+    header::{HeaderStyle, TitleBar},
     interaction::{InputAdapterSource, InputState},
-    items::{menu_item::SelectValue, MenuItem, MenuListItem},
+    items::{apply_conditions, menu_item::SelectValue, MenuItem, MenuListItem},
     selection_indicator::{style::IndicatorStyle, SelectionIndicatorController},
+    storage::SettingsStorage,
+    tabs::{self, TabStart},
     theme::Theme,
-    Menu, MenuState, MenuStyle, NoItems,
+    Menu, MenuState, MenuStyle, NoItems, SavedState,
 };
 use core::marker::PhantomData;
-use embedded_layout::{
-    layout::linear::LinearLayout,
-    object_chain::ChainElement,
-    prelude::*,
-    view_group::{EmptyViewGroup, ViewGroup},
-};
+use embedded_graphics::primitives::Rectangle;
+use embedded_layout::{object_chain::ChainElement, prelude::*, view_group::ViewGroup};
+
+/// The builder returned by [`MenuBuilder::add_tab`] when the menu has no items yet.
+type WithFirstTab<T, IT, I, R, P, S, C, H, E, ST> =
+    MenuBuilder<T, IT, Chain<TabStart<I>>, R, P, S, C, H, E, ST>;
+
+/// The builder returned by [`MenuBuilder::add_tab`] when the menu already has items.
+type WithTab<T, IT, I, CE, R, P, S, C, H, E, ST> =
+    MenuBuilder<T, IT, Link<TabStart<I>, CE>, R, P, S, C, H, E, ST>;
+
+/// The builder returned by [`MenuBuilder::add_section_title`] when the menu has no items yet.
+type WithFirstSectionTitle<T, IT, T2, R, P, S, C, H, E, ST> =
+    MenuBuilder<T, IT, Chain<MenuItem<T2, R, (), false>>, R, P, S, C, H, E, ST>;
+
+/// The builder returned by [`MenuBuilder::add_section_title`] when the menu already has items.
+type WithSectionTitle<T, IT, T2, CE, R, P, S, C, H, E, ST> =
+    MenuBuilder<T, IT, Link<MenuItem<T2, R, (), false>, CE>, R, P, S, C, H, E, ST>;
+
+/// The builder returned by [`MenuBuilder::add_item`] when the menu has no items yet.
+type WithFirstItem<T, IT, T2, V, R, P, S, C, H, E, ST> =
+    MenuBuilder<T, IT, Chain<MenuItem<T2, R, V, true>>, R, P, S, C, H, E, ST>;
+
+/// The builder returned by [`MenuBuilder::add_item`] when the menu already has items.
+type WithItem<T, IT, T2, V, CE, R, P, S, C, H, E, ST> =
+    MenuBuilder<T, IT, Link<MenuItem<T2, R, V, true>, CE>, R, P, S, C, H, E, ST>;
 
-pub struct MenuBuilder<T, IT, LL, R, P, S, C>
+/// The builder returned by [`MenuBuilder::add_menu_item`] when the menu has no items yet.
+type WithFirstMenuItem<T, IT, I, R, P, S, C, H, E, ST> =
+    MenuBuilder<T, IT, Chain<I>, R, P, S, C, H, E, ST>;
+
+/// The builder returned by [`MenuBuilder::add_menu_item`] when the menu already has items.
+type WithMenuItem<T, IT, I, CE, R, P, S, C, H, E, ST> =
+    MenuBuilder<T, IT, Link<I, CE>, R, P, S, C, H, E, ST>;
+
+/// The builder returned by [`MenuBuilder::add_menu_items`] when the menu has no items yet.
+type WithFirstMenuItems<T, IT, IC, I, R, P, S, C, H, E, ST> =
+    MenuBuilder<T, IT, Chain<MenuItems<IC, I, R>>, R, P, S, C, H, E, ST>;
+
+/// The builder returned by [`MenuBuilder::add_menu_items`] when the menu already has items.
+type WithMenuItems<T, IT, IC, I, CE, R, P, S, C, H, E, ST> =
+    MenuBuilder<T, IT, Link<MenuItems<IC, I, R>, CE>, R, P, S, C, H, E, ST>;
+
+/// The builder returned by [`MenuBuilder::add_virtual_items`] when the menu has no items yet.
+type WithFirstVirtualItems<T, IT, IP, R, P, S, C, H, E, ST> =
+    MenuBuilder<T, IT, Chain<VirtualItems<IP, R>>, R, P, S, C, H, E, ST>;
+
+/// The builder returned by [`MenuBuilder::add_virtual_items`] when the menu already has items.
+type WithVirtualItems<T, IT, IP, CE, R, P, S, C, H, E, ST> =
+    MenuBuilder<T, IT, Link<VirtualItems<IP, R>, CE>, R, P, S, C, H, E, ST>;
+
+pub struct MenuBuilder<T, IT, LL, R, P, S, C, H = TitleBar, E = fn(MenuEvent), ST = ()>
 where
     T: AsRef<str>,
     IT: InputAdapterSource<R>,
     S: IndicatorStyle,
     P: SelectionIndicatorController,
     C: Theme,
+    H: HeaderStyle,
 {
     title: T,
     items: LL,
-    style: MenuStyle<S, IT, P, R, C>,
+    style: MenuStyle<S, IT, P, R, C, H>,
+    event_handler: E,
+    storage: ST,
 }
 
-impl<T, R, S, IT, P, C> MenuBuilder<T, IT, NoItems, R, P, S, C>
+impl<T, R, S, IT, P, C, H> MenuBuilder<T, IT, NoItems, R, P, S, C, H>
 where
     T: AsRef<str>,
     S: IndicatorStyle,
     IT: InputAdapterSource<R>,
     P: SelectionIndicatorController,
     C: Theme,
+    H: HeaderStyle,
 {
     /// Creates a new menu builder with the given title and style.
-    pub const fn new(title: T, style: MenuStyle<S, IT, P, R, C>) -> Self {
+    pub const fn new(title: T, style: MenuStyle<S, IT, P, R, C, H>) -> Self {
         Self {
             title,
             items: NoItems,
             style,
+            event_handler: |_| {},
+            storage: (),
         }
     }
 }
 
-impl<T, IT, R, P, S, C> MenuBuilder<T, IT, NoItems, R, P, S, C>
+impl<T, IT, R, P, S, C, H, E, ST> MenuBuilder<T, IT, NoItems, R, P, S, C, H, E, ST>
 where
     T: AsRef<str>,
     IT: InputAdapterSource<R>,
     P: SelectionIndicatorController,
     S: IndicatorStyle,
     C: Theme,
+    H: HeaderStyle,
 {
+    /// Starts a new tab with `item` as its first item. The items added after this call are
+    /// displayed in the new tab.
+    ///
+    /// Tabbed menus display the titles of their tabs in the header instead of the menu title.
+    /// [`Navigation::PreviousTab`] and [`Navigation::NextTab`] switch between the tabs, other
+    /// navigation moves between the items of the displayed tab. Each tab remembers its selected
+    /// item and scroll position.
+    ///
+    /// [`Navigation::PreviousTab`]: crate::interaction::Navigation::PreviousTab
+    /// [`Navigation::NextTab`]: crate::interaction::Navigation::NextTab
+    pub fn add_tab<I: MenuListItem<R>>(
+        self,
+        title: &'static str,
+        mut item: I,
+    ) -> WithFirstTab<T, IT, I, R, P, S, C, H, E, ST> {
+        item.set_style(&self.style.text_style());
+
+        MenuBuilder {
+            title: self.title,
+            items: Chain::new(TabStart::new(title, item)),
+            style: self.style,
+            event_handler: self.event_handler,
+            storage: self.storage,
+        }
+    }
+
     /// Append a non-selectable menu item to the menu with the given title.
     pub fn add_section_title<T2: AsRef<str>>(
         self,
         title: T2,
-    ) -> MenuBuilder<T, IT, Chain<MenuItem<T2, R, (), false>>, R, P, S, C> {
+    ) -> WithFirstSectionTitle<T, IT, T2, R, P, S, C, H, E, ST> {
         self.add_menu_item(
             MenuItem::new(title, ())
                 .with_value_converter(|_| unreachable!())
@@ -70,12 +151,12 @@ where
     /// The menu item will initially have the given value. Upon interaction, the value will be
     /// updated according to the value type's `next` method. The menu will use the callback
     /// passed in the third argument to convert the value to the global return type.
-    pub fn add_item<T2: AsRef<str>, V: SelectValue>(
+    pub fn add_item<T2: AsRef<str>, V: SelectValue + 'static>(
         self,
         title: T2,
         value: V,
         converter: fn(V) -> R,
-    ) -> MenuBuilder<T, IT, Chain<MenuItem<T2, R, V, true>>, R, P, S, C> {
+    ) -> WithFirstItem<T, IT, T2, V, R, P, S, C, H, E, ST> {
         self.add_menu_item(MenuItem::new(title, value).with_value_converter(converter))
     }
 
@@ -83,13 +164,15 @@ where
     pub fn add_menu_item<I: MenuListItem<R>>(
         self,
         mut item: I,
-    ) -> MenuBuilder<T, IT, Chain<I>, R, P, S, C> {
+    ) -> WithFirstMenuItem<T, IT, I, R, P, S, C, H, E, ST> {
         item.set_style(&self.style.text_style());
 
         MenuBuilder {
             title: self.title,
             items: Chain::new(item),
             style: self.style,
+            event_handler: self.event_handler,
+            storage: self.storage,
         }
     }
 
@@ -97,7 +180,7 @@ where
     pub fn add_menu_items<I, IC>(
         self,
         mut items: IC,
-    ) -> MenuBuilder<T, IT, Chain<MenuItems<IC, I, R>>, R, P, S, C>
+    ) -> WithFirstMenuItems<T, IT, IC, I, R, P, S, C, H, E, ST>
     where
         I: MenuListItem<R>,
         IC: AsRef<[I]> + AsMut<[I]>,
@@ -111,11 +194,30 @@ where
             title: self.title,
             items: Chain::new(MenuItems::new(items)),
             style: self.style,
+            event_handler: self.event_handler,
+            storage: self.storage,
+        }
+    }
+
+    /// Append a collection of items that are requested from `provider` on demand.
+    pub fn add_virtual_items<IP>(
+        self,
+        provider: IP,
+    ) -> WithFirstVirtualItems<T, IT, IP, R, P, S, C, H, E, ST>
+    where
+        IP: ItemProvider<R>,
+    {
+        MenuBuilder {
+            items: Chain::new(VirtualItems::new(provider, &self.style.text_style())),
+            title: self.title,
+            style: self.style,
+            event_handler: self.event_handler,
+            storage: self.storage,
         }
     }
 }
 
-impl<T, IT, CE, R, P, S, C> MenuBuilder<T, IT, CE, R, P, S, C>
+impl<T, IT, CE, R, P, S, C, H, E, ST> MenuBuilder<T, IT, CE, R, P, S, C, H, E, ST>
 where
     T: AsRef<str>,
     IT: InputAdapterSource<R>,
@@ -123,12 +225,36 @@ where
     P: SelectionIndicatorController,
     S: IndicatorStyle,
     C: Theme,
+    H: HeaderStyle,
 {
+    /// Starts a new tab with `item` as its first item. The items added after this call are
+    /// displayed in the new tab.
+    ///
+    /// See [`MenuBuilder::add_tab`].
+    pub fn add_tab<I: MenuListItem<R>>(
+        self,
+        title: &'static str,
+        mut item: I,
+    ) -> WithTab<T, IT, I, CE, R, P, S, C, H, E, ST> {
+        item.set_style(&self.style.text_style());
+
+        MenuBuilder {
+            title: self.title,
+            items: Link {
+                parent: self.items,
+                object: TabStart::new(title, item),
+            },
+            style: self.style,
+            event_handler: self.event_handler,
+            storage: self.storage,
+        }
+    }
+
     /// Append a non-selectable menu item to the menu with the given title.
     pub fn add_section_title<T2: AsRef<str>>(
         self,
         title: T2,
-    ) -> MenuBuilder<T, IT, Link<MenuItem<T2, R, (), false>, CE>, R, P, S, C> {
+    ) -> WithSectionTitle<T, IT, T2, CE, R, P, S, C, H, E, ST> {
         self.add_menu_item(
             MenuItem::new(title, ())
                 .with_value_converter(|_| unreachable!())
@@ -141,12 +267,12 @@ where
     /// The menu item will initially have the given value. Upon interaction, the value will be
     /// updated according to the value type's `next` method. The menu will use the callback
     /// passed in the third argument to convert the value to the global return type.
-    pub fn add_item<T2: AsRef<str>, V: SelectValue>(
+    pub fn add_item<T2: AsRef<str>, V: SelectValue + 'static>(
         self,
         title: T2,
         value: V,
         converter: fn(V) -> R,
-    ) -> MenuBuilder<T, IT, Link<MenuItem<T2, R, V, true>, CE>, R, P, S, C> {
+    ) -> WithItem<T, IT, T2, V, CE, R, P, S, C, H, E, ST> {
         self.add_menu_item(MenuItem::new(title, value).with_value_converter(converter))
     }
 
@@ -154,7 +280,7 @@ where
     pub fn add_menu_item<I: MenuListItem<R>>(
         self,
         mut item: I,
-    ) -> MenuBuilder<T, IT, Link<I, CE>, R, P, S, C> {
+    ) -> WithMenuItem<T, IT, I, CE, R, P, S, C, H, E, ST> {
         item.set_style(&self.style.text_style());
 
         MenuBuilder {
@@ -164,6 +290,8 @@ where
                 object: item,
             },
             style: self.style,
+            event_handler: self.event_handler,
+            storage: self.storage,
         }
     }
 
@@ -171,7 +299,7 @@ where
     pub fn add_menu_items<I, IC>(
         self,
         mut items: IC,
-    ) -> MenuBuilder<T, IT, Link<MenuItems<IC, I, R>, CE>, R, P, S, C>
+    ) -> WithMenuItems<T, IT, IC, I, CE, R, P, S, C, H, E, ST>
     where
         I: MenuListItem<R>,
         IC: AsRef<[I]> + AsMut<[I]>,
@@ -188,11 +316,83 @@ where
                 object: MenuItems::new(items),
             },
             style: self.style,
+            event_handler: self.event_handler,
+            storage: self.storage,
+        }
+    }
+
+    /// Append a collection of items that are requested from `provider` on demand.
+    pub fn add_virtual_items<IP>(
+        self,
+        provider: IP,
+    ) -> WithVirtualItems<T, IT, IP, CE, R, P, S, C, H, E, ST>
+    where
+        IP: ItemProvider<R>,
+    {
+        MenuBuilder {
+            items: Link {
+                parent: self.items,
+                object: VirtualItems::new(provider, &self.style.text_style()),
+            },
+            title: self.title,
+            style: self.style,
+            event_handler: self.event_handler,
+            storage: self.storage,
         }
     }
 }
 
-impl<T, IT, VG, R, P, S, C> MenuBuilder<T, IT, VG, R, P, S, C>
+impl<T, IT, LL, R, P, S, C, H, E, ST> MenuBuilder<T, IT, LL, R, P, S, C, H, E, ST>
+where
+    T: AsRef<str>,
+    IT: InputAdapterSource<R>,
+    P: SelectionIndicatorController,
+    S: IndicatorStyle,
+    C: Theme,
+    H: HeaderStyle,
+{
+    /// Sets a function that is called when the selection changes, an item is edited or the menu
+    /// is exited.
+    ///
+    /// The handler may capture state, e.g. a buzzer to beep when the selection changes.
+    pub fn with_event_handler<E2>(
+        self,
+        event_handler: E2,
+    ) -> MenuBuilder<T, IT, LL, R, P, S, C, H, E2, ST>
+    where
+        E2: FnMut(MenuEvent),
+    {
+        MenuBuilder {
+            title: self.title,
+            items: self.items,
+            style: self.style,
+            event_handler,
+            storage: self.storage,
+        }
+    }
+
+    /// Sets the storage of the menu.
+    ///
+    /// The values of the items that are bound to a storage key are loaded from `storage` when the
+    /// menu is built, and written back whenever the user changes them. A
+    /// [`MenuEvent::StoreFailed`] event is reported if `storage` fails to store a value.
+    ///
+    /// To keep ownership of the storage, pass a mutable reference.
+    pub fn with_storage<ST2>(self, storage: ST2) -> MenuBuilder<T, IT, LL, R, P, S, C, H, E, ST2>
+    where
+        ST2: SettingsStorage,
+    {
+        MenuBuilder {
+            title: self.title,
+            items: self.items,
+            style: self.style,
+            event_handler: self.event_handler,
+            storage,
+        }
+    }
+}
+
+impl<T, IT, VG, R, P, S, C, H, E, ST> MenuBuilder<T, IT, VG, R, P, S, C, H, E, ST>
 where
     T: AsRef<str>,
     IT: InputAdapterSource<R>,
@@ -200,11 +400,14 @@ where
     P: SelectionIndicatorController,
     S: IndicatorStyle,
     C: Theme,
+    H: HeaderStyle,
+    ST: SettingsStorage,
 {
     /// Builds the menu and initializes it to a default state.
-    pub fn build(self) -> Menu<T, IT, VG, R, P, S, C> {
+    pub fn build(self) -> Menu<T, IT, VG, R, P, S, C, H, E, ST> {
         self.build_with_state(MenuState {
             selected: 0,
+            selected_id: None,
             list_offset: 0,
             interaction_state: Default::default(),
             indicator_state: Default::default(),
@@ -212,29 +415,95 @@ where
         })
     }
 
+    /// Loads the values of the items that are bound to a storage key.
+    pub fn load_settings(mut self, storage: &impl SettingsStorage) -> Self {
+        self.items.load_settings(storage);
+        self
+    }
+
+    /// Builds the menu, restoring a previously saved state.
+    ///
+    /// Unlike [`MenuBuilder::build_with_state`], the selection indicator is placed on the selected
+    /// item immediately, without animation.
+    pub fn build_with_saved_state(
+        self,
+        saved: SavedState,
+    ) -> Menu<T, IT, VG, R, P, S, C, H, E, ST> {
+        let mut menu = self.build_with_state_by_id(MenuState::from(saved));
+        menu.style
+            .indicator
+            .jump_to_target(&mut menu.state.indicator_state);
+        menu
+    }
+
+    /// Builds the menu, assigning to it the given state.
+    ///
+    /// The selection is restored by the identifier of the previously selected item. If it had no
+    /// identifier, or no item has the identifier, the selection is restored by index.
+    pub fn build_with_state_by_id(
+        self,
+        mut state: MenuState<IT::InputAdapter, P, S>,
+    ) -> Menu<T, IT, VG, R, P, S, C, H, E, ST> {
+        if let Some(index) = state.selected_id.and_then(|id| self.items.index_of(id)) {
+            state.selected = index;
+        }
+
+        self.build_with_state(state)
+    }
+
     /// Builds the menu, assigning to it the given state.
     pub fn build_with_state(
         mut self,
         mut state: MenuState<IT::InputAdapter, P, S>,
-    ) -> Menu<T, IT, VG, R, P, S, C> {
+    ) -> Menu<T, IT, VG, R, P, S, C, H, E, ST> {
         // We have less menu items than before. Avoid crashing.
         let max_idx = self.items.count().saturating_sub(1);
 
-        LinearLayout::vertical(EmptyViewGroup).arrange_view_group(&mut self.items);
+        self.items.load_settings(&self.storage);
+        apply_conditions(&mut self.items);
+        crate::layout::arrange(&mut self.items, self.style.layout);
 
+        let restored_selection = state.selected;
         state.set_selected_item(state.selected, &self.items, &self.style);
-        if max_idx < state.selected {
+        if max_idx < restored_selection {
             self.style
                 .indicator
                 .jump_to_target(&mut state.indicator_state);
         }
 
+        // Display the tab of the selected item, and don't scroll out of it.
+        let current_tab = tabs::tab_of(&self.items, state.selected);
+        let list_top = if self.items.tab_count() == 0 {
+            0
+        } else {
+            tabs::extent(&self.items, current_tab).0
+        };
+
+        // Don't scroll past the selected item.
+        let selected_top = MenuItemCollection::bounds_of(&self.items, state.selected)
+            .top_left
+            .y;
+        state.list_offset = state
+            .list_offset
+            .clamp(list_top, selected_top.max(list_top));
+
         Menu {
             state,
             _return_type: PhantomData,
             title: self.title,
             items: self.items,
             style: self.style,
+            event_handler: self.event_handler,
+            storage: self.storage,
+            display_area: Rectangle::zero(),
+            needs_layout: false,
+            idle_ticks: 0,
+            timed_out: false,
+            idle_return: false,
+            overlay: None,
+            dialog: None,
+            help: None,
+            current_tab,
         }
     }
 }
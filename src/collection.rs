@@ -1,4 +1,4 @@
-use core::marker::PhantomData;
+use core::{any::Any, marker::PhantomData};
 
 use embedded_graphics::{
     mono_font::MonoTextStyle,
@@ -8,16 +8,122 @@ use embedded_graphics::{
 };
 use embedded_layout::{object_chain::ChainElement, prelude::*, view_group::ViewGroup};
 
-use crate::items::{Marker, MenuListItem};
+use crate::{
+    interaction::Direction,
+    items::{Conditions, Confirmation, DynMenuListItem, Marker, MenuLine, MenuListItem},
+    storage::SettingsStorage,
+    tabs::Tab,
+};
 
 /// Menu-related extensions for object chain elements
 pub trait MenuItemCollection<R> {
     fn bounds_of(&self, nth: usize) -> Rectangle;
     fn value_of(&self, nth: usize) -> R;
     fn interact_with(&mut self, nth: usize) -> R;
+    /// Interacts with an item, and returns whether its value changed.
+    ///
+    /// The default implementation compares the stored value of the item.
+    fn interact_changed(&mut self, nth: usize) -> (R, bool) {
+        let old = self.stored_value_of(nth);
+        let value = self.interact_with(nth);
+        (value, self.stored_value_of(nth) != old)
+    }
+    /// Handles left and right navigation on an item. Returns a value if the item's value changed.
+    fn step(&mut self, _nth: usize, _direction: Direction) -> Option<R> {
+        None
+    }
     /// Whether an item is selectable. If not, the item will be skipped.
     fn selectable(&self, nth: usize) -> bool;
     fn count(&self) -> usize;
+    /// Returns the title of an item.
+    fn title_of(&self, _nth: usize) -> &str {
+        ""
+    }
+    /// Hides the items that don't match `filter`, and arranges the remaining ones.
+    fn filter(&mut self, _filter: &str) {}
+    /// Returns the identifier of an item, if it has one.
+    fn id_of(&self, _nth: usize) -> Option<u16> {
+        None
+    }
+    /// Returns the index of the first item with the given identifier.
+    fn index_of(&self, _id: u16) -> Option<usize> {
+        None
+    }
+    /// Loads the values of the items that are bound to a storage key.
+    fn load_settings(&mut self, _storage: &dyn SettingsStorage) {}
+    /// Writes the values of the items that are bound to a storage key. Returns `false` if
+    /// `storage` failed to store any of them.
+    fn store_settings(&self, _storage: &mut dyn SettingsStorage) -> bool {
+        true
+    }
+    /// Writes the value of an item into `storage`, if the item is bound to a storage key. Returns
+    /// `false` if `storage` failed to store it.
+    fn store_setting_of(&self, _nth: usize, _storage: &mut dyn SettingsStorage) -> bool {
+        true
+    }
+    /// Returns the value of an item in its [`SettingsStorage`] representation.
+    fn stored_value_of(&self, _nth: usize) -> Option<u32> {
+        None
+    }
+    /// Changes the value of an item to the one represented by `value`. Returns whether the item
+    /// has such a value.
+    fn set_stored_value(&mut self, _nth: usize, _value: u32) -> bool {
+        false
+    }
+    /// Measures an item again, e.g. after its contents have changed.
+    fn set_style_of(&mut self, _nth: usize, _text_style: &MonoTextStyle<'_, BinaryColor>) {}
+    /// Shows or hides an item, and arranges the items if it changed. Returns whether the item's
+    /// visibility changed.
+    fn set_visible(&mut self, _nth: usize, _visible: bool) -> bool {
+        false
+    }
+    /// Enables or disables an item. Returns whether the item changed.
+    fn set_enabled(&mut self, _nth: usize, _enabled: bool) -> bool {
+        false
+    }
+    /// Returns the value of an item, for [`Condition`](crate::items::Condition)s that compare it
+    /// by its type.
+    fn any_value_of(&self, _nth: usize) -> Option<&dyn Any> {
+        None
+    }
+    /// Changes the value of an item to `value`, if it has a value of the same type. Returns
+    /// whether the value was changed.
+    fn set_any_value_of(&mut self, _nth: usize, _value: &dyn Any) -> bool {
+        false
+    }
+    /// Returns the conditions that control whether an item is visible or enabled.
+    fn conditions_of(&self, _nth: usize) -> Conditions {
+        Conditions::none()
+    }
+    /// Returns the question the menu asks before activating an item, if any.
+    fn confirmation_of(&self, _nth: usize) -> Option<Confirmation> {
+        None
+    }
+    /// Returns the part of an item that the selection indicator highlights, relative to the top
+    /// left corner of the item. `None` means the whole item.
+    fn selection_area_of(&self, _nth: usize) -> Option<Rectangle> {
+        None
+    }
+    /// Returns the help text of an item, if any.
+    fn help_of(&self, _nth: usize) -> Option<&str> {
+        None
+    }
+    /// Moves an item, e.g. into a cell of a grid. Collections that don't move their items can't
+    /// be arranged in a [`MenuLayout::Grid`](crate::MenuLayout::Grid) or
+    /// [`MenuLayout::Carousel`](crate::MenuLayout::Carousel).
+    fn translate_item(&mut self, _nth: usize, _by: Point) {}
+    /// Returns the number of tabs that start in the collection.
+    fn tab_count(&self) -> usize {
+        0
+    }
+    /// Returns the `nth` tab that starts in the collection, and the index of its first item.
+    fn tab(&self, _nth: usize) -> Option<(usize, &Tab)> {
+        None
+    }
+    /// Returns the `nth` tab that starts in the collection.
+    fn tab_mut(&mut self, _nth: usize) -> Option<&mut Tab> {
+        None
+    }
     fn draw_styled<D>(
         &self,
         text_style: &MonoTextStyle<'static, BinaryColor>,
@@ -47,6 +153,16 @@ where
         self.interact()
     }
 
+    fn interact_changed(&mut self, nth: usize) -> (R, bool) {
+        debug_assert!(nth == 0);
+        MenuListItem::interact_changed(self)
+    }
+
+    fn step(&mut self, nth: usize, direction: Direction) -> Option<R> {
+        debug_assert!(nth == 0);
+        MenuListItem::step(self, direction)
+    }
+
     fn selectable(&self, nth: usize) -> bool {
         debug_assert!(nth == 0);
         self.selectable()
@@ -56,6 +172,97 @@ where
         1
     }
 
+    fn title_of(&self, nth: usize) -> &str {
+        debug_assert!(nth == 0);
+        self.title()
+    }
+
+    fn filter(&mut self, filter: &str) {
+        MenuListItem::filter(self, filter);
+    }
+
+    fn id_of(&self, nth: usize) -> Option<u16> {
+        debug_assert!(nth == 0);
+        self.id()
+    }
+
+    fn index_of(&self, id: u16) -> Option<usize> {
+        (self.id() == Some(id)).then_some(0)
+    }
+
+    fn load_settings(&mut self, storage: &dyn SettingsStorage) {
+        self.load(storage);
+    }
+
+    fn store_settings(&self, storage: &mut dyn SettingsStorage) -> bool {
+        self.store(storage)
+    }
+
+    fn store_setting_of(&self, nth: usize, storage: &mut dyn SettingsStorage) -> bool {
+        debug_assert!(nth == 0);
+        self.store(storage)
+    }
+
+    fn stored_value_of(&self, nth: usize) -> Option<u32> {
+        debug_assert!(nth == 0);
+        self.stored_value()
+    }
+
+    fn set_stored_value(&mut self, nth: usize, value: u32) -> bool {
+        debug_assert!(nth == 0);
+        MenuListItem::set_stored_value(self, value)
+    }
+
+    fn set_style_of(&mut self, nth: usize, text_style: &MonoTextStyle<'_, BinaryColor>) {
+        debug_assert!(nth == 0);
+        self.set_style(text_style);
+    }
+
+    fn set_visible(&mut self, nth: usize, visible: bool) -> bool {
+        debug_assert!(nth == 0);
+        MenuListItem::set_visible(self, visible)
+    }
+
+    fn set_enabled(&mut self, nth: usize, enabled: bool) -> bool {
+        debug_assert!(nth == 0);
+        MenuListItem::set_enabled(self, enabled)
+    }
+
+    fn any_value_of(&self, nth: usize) -> Option<&dyn Any> {
+        debug_assert!(nth == 0);
+        self.any_value()
+    }
+
+    fn set_any_value_of(&mut self, nth: usize, value: &dyn Any) -> bool {
+        debug_assert!(nth == 0);
+        self.set_any_value(value)
+    }
+
+    fn conditions_of(&self, nth: usize) -> Conditions {
+        debug_assert!(nth == 0);
+        self.conditions()
+    }
+
+    fn confirmation_of(&self, nth: usize) -> Option<Confirmation> {
+        debug_assert!(nth == 0);
+        self.confirmation()
+    }
+
+    fn selection_area_of(&self, nth: usize) -> Option<Rectangle> {
+        debug_assert!(nth == 0);
+        self.selection_area()
+    }
+
+    fn help_of(&self, nth: usize) -> Option<&str> {
+        debug_assert!(nth == 0);
+        self.help()
+    }
+
+    fn translate_item(&mut self, nth: usize, by: Point) {
+        debug_assert!(nth == 0);
+        self.translate_mut(by);
+    }
+
     fn draw_styled<D>(
         &self,
         text_style: &MonoTextStyle<'static, BinaryColor>,
@@ -79,6 +286,18 @@ where
     _marker: PhantomData<(I, R)>,
 }
 
+/// A collection of items of different types, decided at runtime.
+pub type DynMenuItems<'a, 'b, R> =
+    MenuItems<&'a mut [&'b mut dyn DynMenuListItem<R>], &'b mut dyn DynMenuListItem<R>, R>;
+
+/// A collection of boxed items of different types, decided at runtime.
+#[cfg(feature = "alloc")]
+pub type BoxedMenuItems<'a, R> = MenuItems<
+    alloc::vec::Vec<alloc::boxed::Box<dyn DynMenuListItem<R> + 'a>>,
+    alloc::boxed::Box<dyn DynMenuListItem<R> + 'a>,
+    R,
+>;
+
 impl<C, I, R> MenuItems<C, I, R>
 where
     C: AsRef<[I]> + AsMut<[I]>,
@@ -98,6 +317,16 @@ where
             _marker: PhantomData,
         }
     }
+
+    /// Stacks the items below each other, e.g. after their sizes have changed.
+    fn arrange(&mut self) {
+        let mut top_left = self.position;
+
+        for item in self.items.as_mut().iter_mut() {
+            item.translate_mut(top_left - item.bounds().top_left);
+            top_left.y += item.bounds().size.height as i32;
+        }
+    }
 }
 
 impl<C, I, R> MenuItemCollection<R> for MenuItems<C, I, R>
@@ -117,6 +346,14 @@ where
         self.items.as_mut()[nth].interact()
     }
 
+    fn interact_changed(&mut self, nth: usize) -> (R, bool) {
+        self.items.as_mut()[nth].interact_changed()
+    }
+
+    fn step(&mut self, nth: usize, direction: Direction) -> Option<R> {
+        self.items.as_mut()[nth].step(direction)
+    }
+
     fn selectable(&self, nth: usize) -> bool {
         self.items.as_ref()[nth].selectable()
     }
@@ -125,6 +362,99 @@ where
         self.items.as_ref().len()
     }
 
+    fn title_of(&self, nth: usize) -> &str {
+        self.items.as_ref()[nth].title()
+    }
+
+    fn filter(&mut self, filter: &str) {
+        for item in self.items.as_mut() {
+            item.filter(filter);
+        }
+        self.arrange();
+    }
+
+    fn id_of(&self, nth: usize) -> Option<u16> {
+        self.items.as_ref()[nth].id()
+    }
+
+    fn index_of(&self, id: u16) -> Option<usize> {
+        self.items
+            .as_ref()
+            .iter()
+            .position(|item| item.id() == Some(id))
+    }
+
+    fn load_settings(&mut self, storage: &dyn SettingsStorage) {
+        for item in self.items.as_mut() {
+            item.load(storage);
+        }
+    }
+
+    fn store_settings(&self, storage: &mut dyn SettingsStorage) -> bool {
+        let mut stored = true;
+        for item in self.items.as_ref() {
+            stored &= item.store(storage);
+        }
+        stored
+    }
+
+    fn store_setting_of(&self, nth: usize, storage: &mut dyn SettingsStorage) -> bool {
+        self.items.as_ref()[nth].store(storage)
+    }
+
+    fn stored_value_of(&self, nth: usize) -> Option<u32> {
+        self.items.as_ref()[nth].stored_value()
+    }
+
+    fn set_stored_value(&mut self, nth: usize, value: u32) -> bool {
+        self.items.as_mut()[nth].set_stored_value(value)
+    }
+
+    fn set_style_of(&mut self, nth: usize, text_style: &MonoTextStyle<'_, BinaryColor>) {
+        self.items.as_mut()[nth].set_style(text_style);
+        self.arrange();
+    }
+
+    fn set_visible(&mut self, nth: usize, visible: bool) -> bool {
+        let changed = self.items.as_mut()[nth].set_visible(visible);
+        if changed {
+            self.arrange();
+        }
+        changed
+    }
+
+    fn set_enabled(&mut self, nth: usize, enabled: bool) -> bool {
+        self.items.as_mut()[nth].set_enabled(enabled)
+    }
+
+    fn any_value_of(&self, nth: usize) -> Option<&dyn Any> {
+        self.items.as_ref()[nth].any_value()
+    }
+
+    fn set_any_value_of(&mut self, nth: usize, value: &dyn Any) -> bool {
+        self.items.as_mut()[nth].set_any_value(value)
+    }
+
+    fn conditions_of(&self, nth: usize) -> Conditions {
+        self.items.as_ref()[nth].conditions()
+    }
+
+    fn confirmation_of(&self, nth: usize) -> Option<Confirmation> {
+        self.items.as_ref()[nth].confirmation()
+    }
+
+    fn selection_area_of(&self, nth: usize) -> Option<Rectangle> {
+        self.items.as_ref()[nth].selection_area()
+    }
+
+    fn help_of(&self, nth: usize) -> Option<&str> {
+        self.items.as_ref()[nth].help()
+    }
+
+    fn translate_item(&mut self, nth: usize, by: Point) {
+        self.items.as_mut()[nth].translate_mut(by);
+    }
+
     fn draw_styled<D>(
         &self,
         text_style: &MonoTextStyle<'static, BinaryColor>,
@@ -154,17 +484,23 @@ where
     }
 
     fn bounds(&self) -> Rectangle {
-        let mut size = Size::zero();
+        // The items are usually stacked, but may be arranged in a grid.
+        let mut bottom_right = self.position;
 
         for view in self.items.as_ref().iter() {
-            let view_size = view.bounds().size;
-            size = Size::new(
-                size.width.max(view_size.width),
-                size.height + view_size.height,
+            let bounds = view.bounds();
+            bottom_right = Point::new(
+                bottom_right
+                    .x
+                    .max(bounds.top_left.x + bounds.size.width as i32),
+                bottom_right
+                    .y
+                    .max(bounds.top_left.y + bounds.size.height as i32),
             );
         }
 
-        Rectangle::new(self.position, size)
+        let size = bottom_right - self.position;
+        Rectangle::new(self.position, Size::new(size.x as u32, size.y as u32))
     }
 }
 
@@ -186,6 +522,300 @@ where
     }
 }
 
+/// Provides the items of a [`VirtualItems`] collection on demand.
+pub trait ItemProvider<R> {
+    /// Returns the number of items.
+    fn count(&self) -> usize;
+
+    /// Returns the title of the `nth` item.
+    fn title(&self, nth: usize) -> &str;
+
+    /// Returns the marker displayed on the right side of the `nth` item.
+    fn marker(&self, _nth: usize) -> &str {
+        ""
+    }
+
+    /// Returns the value of the `nth` item, without interacting with it.
+    fn value_of(&self, nth: usize) -> R;
+
+    /// Activates the `nth` item and returns its value.
+    fn interact(&mut self, nth: usize) -> R;
+
+    /// Handles left and right navigation on the `nth` item. Returns a value if the item's value
+    /// changed.
+    fn step(&mut self, _nth: usize, _direction: Direction) -> Option<R> {
+        None
+    }
+
+    /// Returns whether the `nth` item is selectable.
+    fn selectable(&self, _nth: usize) -> bool {
+        true
+    }
+
+    /// Returns the height of every item in pixels. `line_height` is the height of a single line of
+    /// text.
+    fn height(&self, line_height: u32) -> u32 {
+        line_height
+    }
+
+    /// Only provides the items whose title starts with `filter`, ignoring ASCII case, changing
+    /// [`ItemProvider::count`] accordingly. Called by [`Menu::set_filter`](crate::Menu::set_filter).
+    ///
+    /// The default implementation ignores the filter, so every item stays visible.
+    fn filter(&mut self, _filter: &str) {}
+
+    /// Returns the help text of the `nth` item, if it has one.
+    fn help(&self, _nth: usize) -> Option<&str> {
+        None
+    }
+
+    /// Returns the identifier of the `nth` item, if it has one.
+    fn id(&self, _nth: usize) -> Option<u16> {
+        None
+    }
+
+    /// Returns the [`SettingsStorage`] key of the `nth` item, if its value is stored.
+    fn storage_key(&self, _nth: usize) -> Option<&'static str> {
+        None
+    }
+
+    /// Returns the value of the `nth` item in its [`SettingsStorage`] representation, see
+    /// [`MenuListItem::stored_value`].
+    fn stored_value(&self, _nth: usize) -> Option<u32> {
+        None
+    }
+
+    /// Changes the value of the `nth` item to the one represented by `value`. Returns whether the
+    /// item has such a value.
+    fn set_stored_value(&mut self, _nth: usize, _value: u32) -> bool {
+        false
+    }
+
+    /// Returns the question asked before the `nth` item is activated, if it needs confirmation.
+    fn confirmation(&self, _nth: usize) -> Option<Confirmation> {
+        None
+    }
+}
+
+/// A collection of items that are only created when they are displayed
+///
+/// Unlike [`MenuItems`], the items don't need to exist up front. They are requested from an
+/// [`ItemProvider`] when the menu draws or interacts with them, which makes this collection
+/// suitable for lists with thousands of entries.
+///
+/// Every item has the same height, so item positions are calculated from their index.
+///
+/// Only the features that the [`ItemProvider`] implements are available. Virtual items can't be
+/// hidden, disabled at runtime or shown and enabled by [`Condition`]s. They are always stacked
+/// below each other, so they can't be arranged in a [`MenuLayout::Grid`] or a
+/// [`MenuLayout::Carousel`]. Use [`ItemProvider::selectable`] and [`ItemProvider::filter`]
+/// to skip or hide items instead. [`Menu::set_value`] only changes values of providers that
+/// implement [`ItemProvider::set_stored_value`].
+///
+/// [`Condition`]: crate::items::Condition
+/// [`Menu::set_value`]: crate::Menu::set_value
+/// [`MenuLayout::Grid`]: crate::MenuLayout::Grid
+/// [`MenuLayout::Carousel`]: crate::MenuLayout::Carousel
+pub struct VirtualItems<P, R>
+where
+    P: ItemProvider<R>,
+{
+    provider: P,
+    position: Point,
+    line_height: u32,
+    _marker: PhantomData<R>,
+}
+
+impl<P, R> VirtualItems<P, R>
+where
+    P: ItemProvider<R>,
+{
+    pub fn new(provider: P, text_style: &MonoTextStyle<'_, BinaryColor>) -> Self {
+        Self {
+            provider,
+            position: Point::zero(),
+            line_height: MenuLine::new("", text_style).bounds().size.height,
+            _marker: PhantomData,
+        }
+    }
+
+    fn row_height(&self) -> u32 {
+        self.provider.height(self.line_height)
+    }
+
+    fn line(&self, nth: usize, top: i32, text_style: &MonoTextStyle<'_, BinaryColor>) -> MenuLine {
+        let mut line = MenuLine::new(self.provider.marker(nth), text_style);
+        line.set_height(self.row_height());
+        line.translate_mut(Point::new(self.position.x, top));
+        line
+    }
+}
+
+impl<P, R> MenuItemCollection<R> for VirtualItems<P, R>
+where
+    P: ItemProvider<R>,
+{
+    fn bounds_of(&self, nth: usize) -> Rectangle {
+        let height = self.row_height();
+        let top = (nth as u32).saturating_mul(height) as i32;
+
+        Rectangle::new(self.position + Point::new(0, top), Size::new(1, height))
+    }
+
+    fn value_of(&self, nth: usize) -> R {
+        self.provider.value_of(nth)
+    }
+
+    fn interact_with(&mut self, nth: usize) -> R {
+        self.provider.interact(nth)
+    }
+
+    fn step(&mut self, nth: usize, direction: Direction) -> Option<R> {
+        self.provider.step(nth, direction)
+    }
+
+    fn selectable(&self, nth: usize) -> bool {
+        self.provider.selectable(nth)
+    }
+
+    fn count(&self) -> usize {
+        self.provider.count()
+    }
+
+    fn title_of(&self, nth: usize) -> &str {
+        self.provider.title(nth)
+    }
+
+    fn filter(&mut self, filter: &str) {
+        self.provider.filter(filter);
+    }
+
+    fn id_of(&self, nth: usize) -> Option<u16> {
+        self.provider.id(nth)
+    }
+
+    fn index_of(&self, id: u16) -> Option<usize> {
+        (0..self.count()).find(|&nth| self.provider.id(nth) == Some(id))
+    }
+
+    fn load_settings(&mut self, storage: &dyn SettingsStorage) {
+        for nth in 0..self.count() {
+            let stored = self
+                .provider
+                .storage_key(nth)
+                .and_then(|key| storage.get(key));
+            if let Some(stored) = stored {
+                self.provider.set_stored_value(nth, stored);
+            }
+        }
+    }
+
+    fn store_settings(&self, storage: &mut dyn SettingsStorage) -> bool {
+        let mut stored = true;
+        for nth in 0..self.count() {
+            stored &= self.store_setting_of(nth, storage);
+        }
+        stored
+    }
+
+    fn store_setting_of(&self, nth: usize, storage: &mut dyn SettingsStorage) -> bool {
+        match (
+            self.provider.storage_key(nth),
+            self.provider.stored_value(nth),
+        ) {
+            (Some(key), Some(value)) => storage.set(key, value),
+            _ => true,
+        }
+    }
+
+    fn stored_value_of(&self, nth: usize) -> Option<u32> {
+        self.provider.stored_value(nth)
+    }
+
+    fn set_stored_value(&mut self, nth: usize, value: u32) -> bool {
+        self.provider.set_stored_value(nth, value)
+    }
+
+    fn set_style_of(&mut self, _nth: usize, _text_style: &MonoTextStyle<'_, BinaryColor>) {}
+
+    fn set_visible(&mut self, _nth: usize, _visible: bool) -> bool {
+        false
+    }
+
+    fn set_enabled(&mut self, _nth: usize, _enabled: bool) -> bool {
+        false
+    }
+
+    fn conditions_of(&self, _nth: usize) -> Conditions {
+        Conditions::none()
+    }
+
+    fn confirmation_of(&self, nth: usize) -> Option<Confirmation> {
+        self.provider.confirmation(nth)
+    }
+
+    fn selection_area_of(&self, _nth: usize) -> Option<Rectangle> {
+        None
+    }
+
+    fn help_of(&self, nth: usize) -> Option<&str> {
+        self.provider.help(nth)
+    }
+
+    fn translate_item(&mut self, _nth: usize, _by: Point) {
+        // Virtual items are always stacked below each other.
+    }
+
+    fn draw_styled<D>(
+        &self,
+        text_style: &MonoTextStyle<'static, BinaryColor>,
+        display: &mut D,
+    ) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = BinaryColor>,
+    {
+        let display_area = display.bounding_box();
+        let display_top = display_area.top_left.y;
+        let display_bottom = display_top + display_area.size.height as i32;
+
+        let height = self.row_height().max(1) as i32;
+        let first = ((display_top - self.position.y) / height).max(0) as usize;
+
+        let mut top = self.position.y + first as i32 * height;
+        for nth in first..self.count() {
+            if top >= display_bottom {
+                break;
+            }
+
+            self.line(nth, top, text_style).draw_styled(
+                self.provider.title(nth),
+                self.provider.marker(nth),
+                text_style,
+                display,
+            )?;
+
+            top += height;
+        }
+
+        Ok(())
+    }
+}
+
+impl<P, R> View for VirtualItems<P, R>
+where
+    P: ItemProvider<R>,
+{
+    fn translate_impl(&mut self, by: Point) {
+        self.position += by;
+    }
+
+    fn bounds(&self) -> Rectangle {
+        let height = (self.count() as u32).saturating_mul(self.row_height());
+
+        Rectangle::new(self.position, Size::new(1, height))
+    }
+}
+
 impl<I, R> MenuItemCollection<R> for Chain<I>
 where
     I: MenuItemCollection<R>,
@@ -202,6 +832,14 @@ where
         self.object.interact_with(nth)
     }
 
+    fn interact_changed(&mut self, nth: usize) -> (R, bool) {
+        self.object.interact_changed(nth)
+    }
+
+    fn step(&mut self, nth: usize, direction: Direction) -> Option<R> {
+        self.object.step(nth, direction)
+    }
+
     fn selectable(&self, nth: usize) -> bool {
         self.object.selectable(nth)
     }
@@ -210,6 +848,94 @@ where
         self.object.count()
     }
 
+    fn title_of(&self, nth: usize) -> &str {
+        self.object.title_of(nth)
+    }
+
+    fn filter(&mut self, filter: &str) {
+        self.object.filter(filter);
+    }
+
+    fn id_of(&self, nth: usize) -> Option<u16> {
+        self.object.id_of(nth)
+    }
+
+    fn index_of(&self, id: u16) -> Option<usize> {
+        self.object.index_of(id)
+    }
+
+    fn load_settings(&mut self, storage: &dyn SettingsStorage) {
+        self.object.load_settings(storage);
+    }
+
+    fn store_settings(&self, storage: &mut dyn SettingsStorage) -> bool {
+        self.object.store_settings(storage)
+    }
+
+    fn store_setting_of(&self, nth: usize, storage: &mut dyn SettingsStorage) -> bool {
+        self.object.store_setting_of(nth, storage)
+    }
+
+    fn stored_value_of(&self, nth: usize) -> Option<u32> {
+        self.object.stored_value_of(nth)
+    }
+
+    fn set_stored_value(&mut self, nth: usize, value: u32) -> bool {
+        self.object.set_stored_value(nth, value)
+    }
+
+    fn set_style_of(&mut self, nth: usize, text_style: &MonoTextStyle<'_, BinaryColor>) {
+        self.object.set_style_of(nth, text_style);
+    }
+
+    fn set_visible(&mut self, nth: usize, visible: bool) -> bool {
+        self.object.set_visible(nth, visible)
+    }
+
+    fn set_enabled(&mut self, nth: usize, enabled: bool) -> bool {
+        self.object.set_enabled(nth, enabled)
+    }
+
+    fn any_value_of(&self, nth: usize) -> Option<&dyn Any> {
+        self.object.any_value_of(nth)
+    }
+
+    fn set_any_value_of(&mut self, nth: usize, value: &dyn Any) -> bool {
+        self.object.set_any_value_of(nth, value)
+    }
+
+    fn conditions_of(&self, nth: usize) -> Conditions {
+        self.object.conditions_of(nth)
+    }
+
+    fn confirmation_of(&self, nth: usize) -> Option<Confirmation> {
+        self.object.confirmation_of(nth)
+    }
+
+    fn selection_area_of(&self, nth: usize) -> Option<Rectangle> {
+        self.object.selection_area_of(nth)
+    }
+
+    fn help_of(&self, nth: usize) -> Option<&str> {
+        self.object.help_of(nth)
+    }
+
+    fn translate_item(&mut self, nth: usize, by: Point) {
+        self.object.translate_item(nth, by);
+    }
+
+    fn tab_count(&self) -> usize {
+        self.object.tab_count()
+    }
+
+    fn tab(&self, nth: usize) -> Option<(usize, &Tab)> {
+        self.object.tab(nth)
+    }
+
+    fn tab_mut(&mut self, nth: usize) -> Option<&mut Tab> {
+        self.object.tab_mut(nth)
+    }
+
     fn draw_styled<D>(
         &self,
         text_style: &MonoTextStyle<'static, BinaryColor>,
@@ -254,6 +980,24 @@ where
         }
     }
 
+    fn interact_changed(&mut self, nth: usize) -> (R, bool) {
+        let count = self.parent.count();
+        if nth < count {
+            self.parent.interact_changed(nth)
+        } else {
+            self.object.interact_changed(nth - count)
+        }
+    }
+
+    fn step(&mut self, nth: usize, direction: Direction) -> Option<R> {
+        let count = self.parent.count();
+        if nth < count {
+            self.parent.step(nth, direction)
+        } else {
+            self.object.step(nth - count, direction)
+        }
+    }
+
     fn selectable(&self, nth: usize) -> bool {
         let count = self.parent.count();
         if nth < count {
@@ -267,6 +1011,185 @@ where
         self.object.count() + self.parent.count()
     }
 
+    fn title_of(&self, nth: usize) -> &str {
+        let count = self.parent.count();
+        if nth < count {
+            self.parent.title_of(nth)
+        } else {
+            self.object.title_of(nth - count)
+        }
+    }
+
+    fn filter(&mut self, filter: &str) {
+        self.parent.filter(filter);
+        self.object.filter(filter);
+    }
+
+    fn id_of(&self, nth: usize) -> Option<u16> {
+        let count = self.parent.count();
+        if nth < count {
+            self.parent.id_of(nth)
+        } else {
+            self.object.id_of(nth - count)
+        }
+    }
+
+    fn index_of(&self, id: u16) -> Option<usize> {
+        self.parent
+            .index_of(id)
+            .or_else(|| Some(self.parent.count() + self.object.index_of(id)?))
+    }
+
+    fn load_settings(&mut self, storage: &dyn SettingsStorage) {
+        self.parent.load_settings(storage);
+        self.object.load_settings(storage);
+    }
+
+    fn store_settings(&self, storage: &mut dyn SettingsStorage) -> bool {
+        let parent = self.parent.store_settings(storage);
+        self.object.store_settings(storage) && parent
+    }
+
+    fn store_setting_of(&self, nth: usize, storage: &mut dyn SettingsStorage) -> bool {
+        let count = self.parent.count();
+        if nth < count {
+            self.parent.store_setting_of(nth, storage)
+        } else {
+            self.object.store_setting_of(nth - count, storage)
+        }
+    }
+
+    fn stored_value_of(&self, nth: usize) -> Option<u32> {
+        let count = self.parent.count();
+        if nth < count {
+            self.parent.stored_value_of(nth)
+        } else {
+            self.object.stored_value_of(nth - count)
+        }
+    }
+
+    fn set_stored_value(&mut self, nth: usize, value: u32) -> bool {
+        let count = self.parent.count();
+        if nth < count {
+            self.parent.set_stored_value(nth, value)
+        } else {
+            self.object.set_stored_value(nth - count, value)
+        }
+    }
+
+    fn set_style_of(&mut self, nth: usize, text_style: &MonoTextStyle<'_, BinaryColor>) {
+        let count = self.parent.count();
+        if nth < count {
+            self.parent.set_style_of(nth, text_style);
+        } else {
+            self.object.set_style_of(nth - count, text_style);
+        }
+    }
+
+    fn set_visible(&mut self, nth: usize, visible: bool) -> bool {
+        let count = self.parent.count();
+        if nth < count {
+            self.parent.set_visible(nth, visible)
+        } else {
+            self.object.set_visible(nth - count, visible)
+        }
+    }
+
+    fn set_enabled(&mut self, nth: usize, enabled: bool) -> bool {
+        let count = self.parent.count();
+        if nth < count {
+            self.parent.set_enabled(nth, enabled)
+        } else {
+            self.object.set_enabled(nth - count, enabled)
+        }
+    }
+
+    fn any_value_of(&self, nth: usize) -> Option<&dyn Any> {
+        let count = self.parent.count();
+        if nth < count {
+            self.parent.any_value_of(nth)
+        } else {
+            self.object.any_value_of(nth - count)
+        }
+    }
+
+    fn set_any_value_of(&mut self, nth: usize, value: &dyn Any) -> bool {
+        let count = self.parent.count();
+        if nth < count {
+            self.parent.set_any_value_of(nth, value)
+        } else {
+            self.object.set_any_value_of(nth - count, value)
+        }
+    }
+
+    fn conditions_of(&self, nth: usize) -> Conditions {
+        let count = self.parent.count();
+        if nth < count {
+            self.parent.conditions_of(nth)
+        } else {
+            self.object.conditions_of(nth - count)
+        }
+    }
+
+    fn confirmation_of(&self, nth: usize) -> Option<Confirmation> {
+        let count = self.parent.count();
+        if nth < count {
+            self.parent.confirmation_of(nth)
+        } else {
+            self.object.confirmation_of(nth - count)
+        }
+    }
+
+    fn selection_area_of(&self, nth: usize) -> Option<Rectangle> {
+        let count = self.parent.count();
+        if nth < count {
+            self.parent.selection_area_of(nth)
+        } else {
+            self.object.selection_area_of(nth - count)
+        }
+    }
+
+    fn help_of(&self, nth: usize) -> Option<&str> {
+        let count = self.parent.count();
+        if nth < count {
+            self.parent.help_of(nth)
+        } else {
+            self.object.help_of(nth - count)
+        }
+    }
+
+    fn translate_item(&mut self, nth: usize, by: Point) {
+        let count = self.parent.count();
+        if nth < count {
+            self.parent.translate_item(nth, by);
+        } else {
+            self.object.translate_item(nth - count, by);
+        }
+    }
+
+    fn tab_count(&self) -> usize {
+        self.parent.tab_count() + self.object.tab_count()
+    }
+
+    fn tab(&self, nth: usize) -> Option<(usize, &Tab)> {
+        let count = self.parent.tab_count();
+        if nth < count {
+            self.parent.tab(nth)
+        } else {
+            let (start, tab) = self.object.tab(nth - count)?;
+            Some((self.parent.count() + start, tab))
+        }
+    }
+
+    fn tab_mut(&mut self, nth: usize) -> Option<&mut Tab> {
+        let count = self.parent.tab_count();
+        if nth < count {
+            self.parent.tab_mut(nth)
+        } else {
+            self.object.tab_mut(nth - count)
+        }
+    }
+
     fn draw_styled<D>(
         &self,
         text_style: &MonoTextStyle<'static, BinaryColor>,
@@ -0,0 +1,27 @@
+/// Events reported to the event handler of a menu.
+///
+/// See [`MenuBuilder::with_event_handler`](crate::builder::MenuBuilder::with_event_handler).
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum MenuEvent {
+    /// The selection moved from the `old` item to the `new` one.
+    SelectionChanged { old: usize, new: usize },
+    /// The value of the item at `index` changed, because it was stepped or activated.
+    ValueChanged { index: usize },
+    /// The item at `index` was activated, but asks for confirmation before it is committed.
+    ///
+    /// Followed by [`MenuEvent::EditCommitted`] if the user confirms the change.
+    ConfirmationRequested { index: usize },
+    /// The item at `index` was activated. If activating the item changed its value,
+    /// [`MenuEvent::ValueChanged`] is reported first.
+    EditCommitted { index: usize },
+    /// The menu returned a value without activating an item, e.g. because the user navigated back.
+    Exited,
+    /// The menu didn't receive input for the time set by
+    /// [`MenuStyle::with_idle_timeout`](crate::MenuStyle::with_idle_timeout).
+    IdleTimeout,
+    /// The storage of the menu failed to store the value of the item at `index`, e.g. because it
+    /// is full.
+    StoreFailed { index: usize },
+    /// A tabbed menu switched from displaying the `old` tab to the `new` one.
+    TabChanged { old: usize, new: usize },
+}
@@ -0,0 +1,150 @@
+//! The soft-key bar drawn below the menu items.
+//!
+//! See [`MenuStyle::with_footer`](crate::MenuStyle::with_footer).
+
+use embedded_graphics::{
+    geometry::AnchorPoint,
+    mono_font::{MonoFont, MonoTextStyle},
+    prelude::{DrawTarget, Point, Primitive},
+    primitives::{Line, PrimitiveStyle, Rectangle},
+    text::{renderer::TextRenderer, Baseline, Text},
+    Drawable,
+};
+
+use crate::theme::Theme;
+
+/// What the user is doing with the menu.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MenuMode {
+    /// The user is moving between the items.
+    Navigating,
+    /// The user is interacting with the selected item, e.g. holding a button.
+    Editing,
+    /// A confirmation dialog is open.
+    Confirming,
+    /// A toast or popup message is displayed over the menu.
+    Message,
+    /// The help text of an item is displayed.
+    Help,
+}
+
+/// The state of the menu that the soft-key labels can depend on.
+pub struct FooterContext<'a> {
+    pub mode: MenuMode,
+    /// The index of the selected item.
+    pub selected: usize,
+    /// The identifier of the selected item, if it has one.
+    pub selected_id: Option<u16>,
+    /// The title of the selected item.
+    pub selected_title: &'a str,
+}
+
+/// Returns the labels of the soft keys, from left to right.
+pub type SoftKeys = fn(&FooterContext<'_>) -> &'static [&'static str];
+
+/// Returns the height of the footer, including the line above the labels.
+pub(crate) fn height(labels: &[&str], font: &MonoFont<'_>) -> u32 {
+    if labels.is_empty() {
+        0
+    } else {
+        font.character_size.height + 1
+    }
+}
+
+/// Draws the soft-key labels into `area`. Each label is centered in an equal share of the width,
+/// so that the labels line up with the buttons below the display.
+pub(crate) fn draw<T, D>(
+    labels: &[&str],
+    area: Rectangle,
+    font: &'static MonoFont<'static>,
+    theme: &T,
+    display: &mut D,
+) -> Result<(), D::Error>
+where
+    T: Theme,
+    D: DrawTarget<Color = T::Color>,
+{
+    Line::new(area.top_left, area.anchor_point(AnchorPoint::TopRight))
+        .into_styled(PrimitiveStyle::with_stroke(theme.text_color(), 1))
+        .draw(display)?;
+
+    let text_style = MonoTextStyle::new(font, theme.text_color());
+    let slot_width = area.size.width / labels.len() as u32;
+
+    for (nth, label) in labels.iter().enumerate() {
+        let width = text_style
+            .measure_string(label, Point::zero(), Baseline::Top)
+            .bounding_box
+            .size
+            .width;
+        let left = nth as u32 * slot_width + slot_width.saturating_sub(width) / 2;
+
+        Text::with_baseline(
+            label,
+            area.top_left + Point::new(left as i32, 1),
+            text_style,
+            Baseline::Top,
+        )
+        .draw(display)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use embedded_graphics::{
+        mock_display::MockDisplay, pixelcolor::BinaryColor, prelude::Point, Drawable,
+    };
+
+    use crate::{
+        footer::MenuMode,
+        interaction::{Action, Interaction, Navigation},
+        items::{Confirm, MenuItem},
+        Menu, MenuStyle,
+    };
+
+    #[test]
+    fn footer_shows_soft_keys() {
+        let mut menu = Menu::with_style(
+            "Title",
+            MenuStyle::default().with_footer(|context| match context.mode {
+                MenuMode::Confirming => &["No", "Yes"],
+                _ if context.selected == 0 => &[],
+                _ => &["Back", "Select"],
+            }),
+        )
+        .add_item("Save", (), |_| 1)
+        .add_menu_item(Confirm::new(
+            MenuItem::new("Reset", ()).with_value_converter(|_| 2),
+            "Reset all settings?",
+        ))
+        .build();
+
+        let draw = |menu: &mut Menu<_, _, _, _, _, _, _>| {
+            let mut display = MockDisplay::<BinaryColor>::new();
+            display.set_allow_overdraw(true);
+            menu.update(&display);
+            menu.draw(&mut display).unwrap();
+            display
+        };
+
+        // No labels, no footer
+        let display = draw(&mut menu);
+        assert_eq!(display.get_pixel(Point::new(0, 53)), None);
+        assert_eq!(menu.content_area(menu.display_area).size.height, 53);
+
+        menu.interact(Interaction::Navigation(Navigation::Next));
+        let display = draw(&mut menu);
+        assert_eq!(display.get_pixel(Point::new(0, 53)), Some(BinaryColor::On));
+        assert_eq!(menu.content_area(menu.display_area).size.height, 42);
+        assert_eq!(menu.item_at(Point::new(5, 55)), None);
+
+        // "Back" is centered in the left half, "Select" fills the right half
+        assert_eq!(display.get_pixel(Point::new(4, 55)), Some(BinaryColor::On));
+        assert_eq!(display.get_pixel(Point::new(33, 55)), Some(BinaryColor::On));
+
+        menu.interact(Interaction::Action(Action::Select));
+        assert_eq!(menu.soft_keys(), ["No", "Yes"]);
+    }
+}
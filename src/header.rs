@@ -0,0 +1,522 @@
+//! The header drawn above the menu items.
+//!
+//! The header is drawn by a [`HeaderStyle`] implementation, selected with
+//! [`MenuStyle::with_header`](crate::MenuStyle::with_header). The default [`TitleBar`] draws the
+//! title of the menu above a horizontal line, and can be configured to display a logo,
+//! breadcrumbs and a status area.
+
+use core::fmt::{self, Write};
+
+use embedded_graphics::{
+    draw_target::DrawTargetExt,
+    geometry::{AnchorPoint, AnchorY},
+    image::{Image, ImageRaw},
+    mono_font::{MonoFont, MonoTextStyle},
+    pixelcolor::BinaryColor,
+    prelude::{DrawTarget, OriginDimensions, Point, Primitive, Size},
+    primitives::{Line, PrimitiveStyle, Rectangle},
+    text::{renderer::TextRenderer, Baseline, Text},
+    Drawable,
+};
+use embedded_text::{
+    alignment::HorizontalAlignment,
+    style::{HeightMode, TextBoxStyle},
+    TextBox,
+};
+
+use crate::{
+    adapters::color_map::BinaryColorDrawTargetExt,
+    tabs::{draw_tab_bar, TabBar},
+    text::TextBuffer,
+    theme::Theme,
+};
+
+/// The state of the menu that a header can display.
+pub struct HeaderContext<'a> {
+    /// The title of the menu.
+    pub title: &'a str,
+    /// The title font of the menu.
+    pub font: &'static MonoFont<'static>,
+    /// The index of the selected item.
+    pub selected: usize,
+    /// The number of items in the menu.
+    pub count: usize,
+    /// How far the list is scrolled down, in pixels. In tabbed menus, this is relative to the top
+    /// of the displayed tab.
+    pub list_offset: i32,
+    tabs: &'a dyn TabBar,
+}
+
+impl<'a> HeaderContext<'a> {
+    pub(crate) fn new(
+        title: &'a str,
+        font: &'static MonoFont<'static>,
+        selected: usize,
+        count: usize,
+        list_offset: i32,
+        tabs: &'a dyn TabBar,
+    ) -> Self {
+        Self {
+            title,
+            font,
+            selected,
+            count,
+            list_offset,
+            tabs,
+        }
+    }
+
+    /// Returns whether the menu displays a tab bar in place of its title.
+    pub fn has_tabs(&self) -> bool {
+        self.tabs.count() > 0
+    }
+
+    /// Returns the height of the title wrapped to `width`, or the height of the tab bar.
+    pub fn title_height(&self, width: u32) -> u32 {
+        if self.has_tabs() {
+            self.font.character_size.height
+        } else if self.title.is_empty() {
+            0
+        } else {
+            TextBox::with_textbox_style(
+                self.title,
+                Rectangle::new(Point::zero(), Size::new(width, 0)),
+                MonoTextStyle::new(self.font, BinaryColor::On),
+                TextBoxStyle::with_height_mode(HeightMode::FitToText),
+            )
+            .bounds
+            .size
+            .height
+        }
+    }
+
+    /// Draws the title of the menu, or the tab bar of a tabbed menu, into `area`.
+    ///
+    /// `background` is used to draw the text of the displayed tab.
+    pub fn draw_title<D>(
+        &self,
+        area: Rectangle,
+        text_color: D::Color,
+        background: D::Color,
+        alignment: HorizontalAlignment,
+        display: &mut D,
+    ) -> Result<(), D::Error>
+    where
+        D: DrawTarget,
+    {
+        let text_style = MonoTextStyle::new(self.font, text_color);
+
+        if self.has_tabs() {
+            draw_tab_bar(self.tabs, area, text_style, background, display)
+        } else {
+            TextBox::with_textbox_style(
+                self.title,
+                area,
+                text_style,
+                TextBoxStyle::with_alignment(alignment),
+            )
+            .draw(display)?;
+
+            Ok(())
+        }
+    }
+}
+
+/// Draws the header of a menu.
+pub trait HeaderStyle: Copy {
+    /// Returns the height of the header in a menu that is `width` pixels wide. Headers with zero
+    /// height are not drawn.
+    fn height(&self, context: &HeaderContext<'_>, width: u32) -> u32;
+
+    /// Draws the header into `area`. The display is clipped to `area`.
+    fn draw<T, D>(
+        &self,
+        context: &HeaderContext<'_>,
+        area: Rectangle,
+        theme: &T,
+        display: &mut D,
+    ) -> Result<(), D::Error>
+    where
+        T: Theme,
+        D: DrawTarget<Color = T::Color>;
+}
+
+/// Displays the position of the selected item, e.g. `3/12`.
+///
+/// Pass this function to [`TitleBar::with_status`] to display a position counter.
+pub fn position(context: &HeaderContext<'_>, status: &mut dyn Write) -> fmt::Result {
+    write!(status, "{}/{}", context.selected + 1, context.count)
+}
+
+/// The longest status text, in bytes. Longer text is cut off.
+const STATUS_LENGTH: usize = 16;
+
+/// Space between the logo, the title and the status area.
+const GAP: u32 = 2;
+
+const BREADCRUMB_SEPARATOR: &str = " > ";
+
+/// The status text of a [`TitleBar`], written by its status function.
+type StatusText = TextBuffer<STATUS_LENGTH>;
+
+fn text_width(text: &str, font: &'static MonoFont<'static>) -> u32 {
+    MonoTextStyle::new(font, BinaryColor::On)
+        .measure_string(text, Point::zero(), Baseline::Top)
+        .next_position
+        .x as u32
+}
+
+/// The default header: the title of the menu above a horizontal line.
+///
+/// The title bar can display a logo before the title, breadcrumbs leading to the menu, and a
+/// status area on the right, e.g. the [`position`] of the selected item. Breadcrumbs are not
+/// displayed in tabbed menus.
+///
+/// `F` is the type of the function that writes the status, see [`TitleBar::with_status`].
+#[derive(Clone, Copy, Debug)]
+pub struct TitleBar<F = fn(&HeaderContext<'_>, &mut dyn Write) -> fmt::Result> {
+    alignment: HorizontalAlignment,
+    inverted: bool,
+    status: Option<F>,
+    logo: Option<ImageRaw<'static, BinaryColor>>,
+    breadcrumbs: &'static [&'static str],
+    hide_on_scroll: bool,
+}
+
+impl Default for TitleBar {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TitleBar {
+    pub const fn new() -> Self {
+        Self {
+            alignment: HorizontalAlignment::Left,
+            inverted: false,
+            status: None,
+            logo: None,
+            breadcrumbs: &[],
+            hide_on_scroll: false,
+        }
+    }
+
+    /// Displays the text written by `status` on the right side of the title bar.
+    ///
+    /// The status is written on every update, and is cut off after 16 bytes. `status` can be a
+    /// closure that captures application state, e.g. a reference to a battery level in a
+    /// [`Cell`](core::cell::Cell).
+    pub const fn with_status<F>(self, status: F) -> TitleBar<F>
+    where
+        F: Fn(&HeaderContext<'_>, &mut dyn Write) -> fmt::Result + Copy,
+    {
+        TitleBar {
+            alignment: self.alignment,
+            inverted: self.inverted,
+            status: Some(status),
+            logo: self.logo,
+            breadcrumbs: self.breadcrumbs,
+            hide_on_scroll: self.hide_on_scroll,
+        }
+    }
+}
+
+impl<F> TitleBar<F>
+where
+    F: Copy,
+{
+    /// Sets the horizontal alignment of the title.
+    pub const fn with_alignment(self, alignment: HorizontalAlignment) -> Self {
+        Self { alignment, ..self }
+    }
+
+    /// Centers the title.
+    pub const fn centered(self) -> Self {
+        self.with_alignment(HorizontalAlignment::Center)
+    }
+
+    /// Draws the title bar in the selection colors of the theme, instead of underlining it.
+    pub const fn inverted(self) -> Self {
+        Self {
+            inverted: true,
+            ..self
+        }
+    }
+
+    /// Displays `logo` before the title, in the text color of the theme.
+    pub const fn with_logo(self, logo: ImageRaw<'static, BinaryColor>) -> Self {
+        Self {
+            logo: Some(logo),
+            ..self
+        }
+    }
+
+    /// Displays the titles of the parent menus before the title, e.g. `Settings > Network > WiFi`.
+    ///
+    /// The title is displayed in a single line. If it doesn't fit, the beginning of the
+    /// breadcrumbs is cut off.
+    pub const fn with_breadcrumbs(self, breadcrumbs: &'static [&'static str]) -> Self {
+        Self {
+            breadcrumbs,
+            ..self
+        }
+    }
+
+    /// Hides the title bar while the list is scrolled down.
+    pub const fn hide_on_scroll(self) -> Self {
+        Self {
+            hide_on_scroll: true,
+            ..self
+        }
+    }
+}
+
+impl<F> TitleBar<F>
+where
+    F: Fn(&HeaderContext<'_>, &mut dyn Write) -> fmt::Result,
+{
+    fn status_text(&self, context: &HeaderContext<'_>) -> Option<StatusText> {
+        self.status.as_ref().map(|status| {
+            let mut text = StatusText::new();
+            // Cut off text is not an error.
+            _ = status(context, &mut text);
+            text
+        })
+    }
+
+    fn shows_breadcrumbs(&self, context: &HeaderContext<'_>) -> bool {
+        !self.breadcrumbs.is_empty() && !context.has_tabs()
+    }
+
+    /// Returns the width of the logo and the status area, including the space around them.
+    fn side_widths(&self, context: &HeaderContext<'_>, status: &str) -> (u32, u32) {
+        let logo = self.logo.map_or(0, |logo| logo.size().width + GAP);
+        let status = if status.is_empty() {
+            0
+        } else {
+            text_width(status, context.font) + GAP
+        };
+
+        (logo, status)
+    }
+
+    fn draw_breadcrumbs<D>(
+        &self,
+        context: &HeaderContext<'_>,
+        area: Rectangle,
+        text_color: D::Color,
+        display: &mut D,
+    ) -> Result<(), D::Error>
+    where
+        D: DrawTarget,
+    {
+        let segments = || {
+            self.breadcrumbs
+                .iter()
+                .flat_map(|crumb| [*crumb, BREADCRUMB_SEPARATOR])
+                .chain([context.title])
+        };
+
+        let width = segments()
+            .map(|segment| text_width(segment, context.font))
+            .sum::<u32>();
+
+        // Keep the end of the line visible if it doesn't fit
+        let free = area.size.width as i32 - width as i32;
+        let left = match self.alignment {
+            _ if free < 0 => free,
+            HorizontalAlignment::Center => free / 2,
+            HorizontalAlignment::Right => free,
+            HorizontalAlignment::Left | HorizontalAlignment::Justified => 0,
+        };
+
+        let text_style = MonoTextStyle::new(context.font, text_color);
+        let mut display = display.clipped(&area);
+        let mut position = area.top_left + Point::new(left, 0);
+        for segment in segments() {
+            position = Text::with_baseline(segment, position, text_style, Baseline::Top)
+                .draw(&mut display)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<F> HeaderStyle for TitleBar<F>
+where
+    F: Fn(&HeaderContext<'_>, &mut dyn Write) -> fmt::Result + Copy,
+{
+    fn height(&self, context: &HeaderContext<'_>, width: u32) -> u32 {
+        if self.hide_on_scroll && context.list_offset > 0 {
+            return 0;
+        }
+
+        let status = self.status_text(context);
+        let status = status.as_ref().map_or("", StatusText::as_str);
+        let (logo_width, status_width) = self.side_widths(context, status);
+
+        let title_height = if self.shows_breadcrumbs(context) {
+            context.font.character_size.height
+        } else {
+            context.title_height(width.saturating_sub(logo_width + status_width))
+        };
+        let status_height = if status.is_empty() {
+            0
+        } else {
+            context.font.character_size.height
+        };
+        let logo_height = self.logo.map_or(0, |logo| logo.size().height);
+
+        let content_height = title_height.max(status_height).max(logo_height);
+        if content_height == 0 {
+            0
+        } else {
+            // Leave room for the bottom line
+            content_height + 1
+        }
+    }
+
+    fn draw<T, D>(
+        &self,
+        context: &HeaderContext<'_>,
+        area: Rectangle,
+        theme: &T,
+        display: &mut D,
+    ) -> Result<(), D::Error>
+    where
+        T: Theme,
+        D: DrawTarget<Color = T::Color>,
+    {
+        let (text_color, background) = if self.inverted {
+            area.into_styled(PrimitiveStyle::with_fill(theme.selection_color()))
+                .draw(display)?;
+            (theme.selected_text_color(), theme.selection_color())
+        } else {
+            Line::new(
+                area.anchor_point(AnchorPoint::BottomLeft),
+                area.anchor_point(AnchorPoint::BottomRight),
+            )
+            .into_styled(PrimitiveStyle::with_stroke(theme.text_color(), 1))
+            .draw(display)?;
+            (theme.text_color(), T::Color::default())
+        };
+
+        let row = area.resized_height(area.size.height.saturating_sub(1), AnchorY::Top);
+        let status = self.status_text(context);
+        let status = status.as_ref().map_or("", StatusText::as_str);
+        let (logo_width, status_width) = self.side_widths(context, status);
+
+        if let Some(logo) = self.logo.as_ref() {
+            Image::new(logo, row.top_left).draw(&mut display.map_colors(
+                &Rectangle::zero(),
+                text_color,
+                background,
+            ))?;
+        }
+
+        if !status.is_empty() {
+            let left = row.top_left.x + row.size.width as i32 - (status_width - GAP) as i32;
+            Text::with_baseline(
+                status,
+                Point::new(left, row.top_left.y),
+                MonoTextStyle::new(context.font, text_color),
+                Baseline::Top,
+            )
+            .draw(display)?;
+        }
+
+        let title_area = Rectangle::new(
+            row.top_left + Point::new(logo_width as i32, 0),
+            Size::new(
+                row.size.width.saturating_sub(logo_width + status_width),
+                row.size.height,
+            ),
+        );
+
+        if self.shows_breadcrumbs(context) {
+            self.draw_breadcrumbs(context, title_area, text_color, display)
+        } else {
+            context.draw_title(title_area, text_color, background, self.alignment, display)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use core::{
+        cell::Cell,
+        fmt::{self, Write},
+    };
+
+    use embedded_graphics::{
+        mock_display::MockDisplay, pixelcolor::BinaryColor, prelude::Point, Drawable,
+    };
+
+    use crate::{
+        header::{position, HeaderContext, TitleBar},
+        interaction::{Interaction, Navigation},
+        Menu, MenuStyle,
+    };
+
+    #[test]
+    fn title_bar_styles() {
+        fn draw<F>(
+            header: TitleBar<F>,
+            navigate: usize,
+        ) -> (Option<usize>, MockDisplay<BinaryColor>)
+        where
+            F: Fn(&HeaderContext<'_>, &mut dyn Write) -> fmt::Result + Copy,
+        {
+            let mut menu = Menu::with_style("WiFi", MenuStyle::default().with_header(header))
+                .add_item("A", false, |_| ())
+                .add_item("B", false, |_| ())
+                .add_item("C", false, |_| ())
+                .add_item("D", false, |_| ())
+                .add_item("E", false, |_| ())
+                .add_item("F", false, |_| ())
+                .add_item("G", false, |_| ())
+                .build();
+
+            let display = MockDisplay::<BinaryColor>::new();
+            for _ in 0..navigate {
+                menu.interact(Interaction::Navigation(Navigation::Next));
+                menu.update(&display);
+            }
+            menu.update(&display);
+
+            let mut display = MockDisplay::<BinaryColor>::new();
+            display.set_allow_overdraw(true);
+            menu.draw(&mut display).unwrap();
+
+            (menu.item_at(Point::new(5, 2)), display)
+        }
+
+        // Inverted title bar with the position of the selected item on the right
+        let (_, display) = draw(TitleBar::new().inverted().with_status(position), 6);
+        assert_eq!(display.get_pixel(Point::new(0, 0)), Some(BinaryColor::On));
+        assert_eq!(display.get_pixel(Point::new(0, 1)), Some(BinaryColor::Off));
+        assert_eq!(display.get_pixel(Point::new(45, 1)), Some(BinaryColor::On));
+        assert_eq!(display.get_pixel(Point::new(46, 1)), Some(BinaryColor::Off));
+
+        // The beginning of the breadcrumbs is cut off
+        let (_, display) = draw(TitleBar::new().with_breadcrumbs(&["Settings", "Net"]), 0);
+        assert_eq!(display.get_pixel(Point::new(0, 1)), None);
+        assert_eq!(display.get_pixel(Point::new(4, 1)), Some(BinaryColor::On));
+        assert_eq!(display.get_pixel(Point::new(0, 10)), Some(BinaryColor::On));
+
+        // The status can display application state
+        let battery = Cell::new(100);
+        let status =
+            |_: &HeaderContext<'_>, status: &mut dyn Write| write!(status, "{}%", battery.get());
+        let (_, display) = draw(TitleBar::new().with_status(status), 0);
+        assert_eq!(display.get_pixel(Point::new(42, 5)), Some(BinaryColor::On));
+
+        battery.set(5);
+        let (_, display) = draw(TitleBar::new().with_status(status), 0);
+        assert_eq!(display.get_pixel(Point::new(42, 5)), None);
+
+        // The title bar disappears when the list is scrolled
+        assert_eq!(draw(TitleBar::new().hide_on_scroll(), 0).0, None);
+        assert_eq!(draw(TitleBar::new().hide_on_scroll(), 6).0, Some(0));
+    }
+}
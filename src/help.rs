@@ -0,0 +1,220 @@
+//! Help texts of the menu items.
+//!
+//! See [`MenuListItem::help`](crate::items::MenuListItem::help) and
+//! [`MenuStyle::with_help_line`](crate::MenuStyle::with_help_line).
+
+use embedded_graphics::{
+    geometry::AnchorPoint,
+    mono_font::{MonoFont, MonoTextStyle},
+    pixelcolor::BinaryColor,
+    prelude::{DrawTarget, DrawTargetExt, Point, Primitive, Size},
+    primitives::{Line, PrimitiveStyle, Rectangle},
+    text::{Baseline, Text},
+    Drawable,
+};
+use embedded_text::{style::TextBoxStyle, TextBox};
+
+use crate::{interaction::Navigation, theme::Theme};
+
+/// Returns the height of the help line, including the line above the text.
+pub(crate) fn line_height(font: &MonoFont<'_>) -> u32 {
+    font.character_size.height + 1
+}
+
+/// Draws the first line of `help` into `area`.
+pub(crate) fn draw_line<T, D>(
+    help: &str,
+    area: Rectangle,
+    font: &'static MonoFont<'static>,
+    theme: &T,
+    display: &mut D,
+) -> Result<(), D::Error>
+where
+    T: Theme,
+    D: DrawTarget<Color = T::Color>,
+{
+    Line::new(area.top_left, area.anchor_point(AnchorPoint::TopRight))
+        .into_styled(PrimitiveStyle::with_stroke(theme.text_color(), 1))
+        .draw(display)?;
+
+    let first_line = help.lines().next().unwrap_or("");
+    Text::with_baseline(
+        first_line,
+        area.top_left + Point::new(0, 1),
+        MonoTextStyle::new(font, theme.text_color()),
+        Baseline::Top,
+    )
+    .draw(display)?;
+
+    Ok(())
+}
+
+/// The help text of an item, displayed in place of the menu.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct HelpPage {
+    /// The index of the item whose help text is displayed.
+    pub index: usize,
+    /// The number of pixels the text is scrolled by.
+    scroll: u32,
+}
+
+impl HelpPage {
+    pub const fn new(index: usize) -> Self {
+        Self { index, scroll: 0 }
+    }
+
+    /// Scrolls the text of a page that is displayed in `area` by whole lines.
+    ///
+    /// Wrapping navigation returns to the beginning of the text after reaching the end.
+    pub fn navigate(
+        &mut self,
+        navigation: Navigation,
+        help: &str,
+        area: Rectangle,
+        font: &'static MonoFont<'static>,
+    ) {
+        let line = font.character_size.height;
+        let text_area = text_area(area, font);
+        let text_height = TextBoxStyle::default().measure_text_height(
+            &MonoTextStyle::new(font, BinaryColor::On),
+            help,
+            text_area.size.width,
+        );
+        // Only whole lines are displayed, and the text height is a multiple of the line height.
+        let visible_lines = (text_area.size.height / line).max(1);
+        let max_scroll = text_height.saturating_sub(visible_lines * line);
+
+        let lines = |count: usize| {
+            u32::try_from(count)
+                .unwrap_or(u32::MAX)
+                .saturating_mul(line)
+        };
+        let wrap = |scroll: u32| if scroll > max_scroll { 0 } else { scroll };
+
+        self.scroll = match navigation {
+            Navigation::Previous => self.scroll.saturating_sub(line),
+            Navigation::Next => wrap(self.scroll + line),
+            Navigation::BackwardWrapping(count) | Navigation::Backward(count) => {
+                self.scroll.saturating_sub(lines(count))
+            }
+            Navigation::ForwardWrapping(count) => wrap(self.scroll.saturating_add(lines(count))),
+            Navigation::Forward(count) => self.scroll.saturating_add(lines(count)),
+            Navigation::Beginning => 0,
+            Navigation::End => max_scroll,
+            _ => self.scroll,
+        }
+        .min(max_scroll);
+    }
+
+    /// Draws the title of the item and the visible part of its help text, covering `area`.
+    pub fn draw<T, D>(
+        &self,
+        title: &str,
+        help: &str,
+        area: Rectangle,
+        font: &'static MonoFont<'static>,
+        theme: &T,
+        display: &mut D,
+    ) -> Result<(), D::Error>
+    where
+        T: Theme,
+        D: DrawTarget<Color = T::Color>,
+    {
+        let text_style = MonoTextStyle::new(font, theme.text_color());
+        let mut display = display.clipped(&area);
+
+        area.into_styled(PrimitiveStyle::with_fill(T::Color::default()))
+            .draw(&mut display)?;
+
+        Text::with_baseline(title, area.top_left, text_style, Baseline::Top).draw(&mut display)?;
+
+        let text_area = text_area(area, font);
+        let separator = text_area.top_left - Point::new(0, 1);
+        Line::new(
+            separator,
+            separator + Point::new(area.size.width as i32 - 1, 0),
+        )
+        .into_styled(PrimitiveStyle::with_stroke(theme.text_color(), 1))
+        .draw(&mut display)?;
+
+        let mut text_box = TextBox::new(help, text_area, text_style);
+        text_box.set_vertical_offset(-(self.scroll as i32));
+        text_box.draw(&mut display.clipped(&text_area))?;
+
+        Ok(())
+    }
+}
+
+/// Returns the part of the help page below the title.
+fn text_area(area: Rectangle, font: &MonoFont<'_>) -> Rectangle {
+    let title_height = line_height(font);
+
+    Rectangle::new(
+        area.top_left + Point::new(0, title_height as i32),
+        Size::new(
+            area.size.width,
+            area.size.height.saturating_sub(title_height),
+        ),
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use embedded_graphics::{
+        mock_display::MockDisplay, pixelcolor::BinaryColor, prelude::Point, Drawable,
+    };
+
+    use crate::{
+        interaction::{Action, Interaction, Navigation},
+        items::MenuItem,
+        Menu, MenuStyle,
+    };
+
+    #[test]
+    fn help_text() {
+        let mut menu = Menu::with_style("Title", MenuStyle::default().with_help_line())
+            .add_menu_item(
+                MenuItem::new("Volume", ())
+                    .with_value_converter(|_| 1)
+                    .with_help("Speaker volume\n2\n3\n4\n5\n6\n7\n8"),
+            )
+            .add_item("Reset", (), |_| 2)
+            .build();
+
+        let draw = |menu: &mut Menu<_, _, _, _, _, _, _>| {
+            let mut display = MockDisplay::<BinaryColor>::new();
+            display.set_allow_overdraw(true);
+            menu.update(&display);
+            menu.draw(&mut display).unwrap();
+            display
+        };
+
+        // The help line is displayed above the bottom of the display
+        let display = draw(&mut menu);
+        assert_eq!(display.get_pixel(Point::new(0, 53)), Some(BinaryColor::On));
+        assert_eq!(menu.content_area(menu.display_area).size.height, 42);
+        assert_eq!(menu.item_at(Point::new(5, 55)), None);
+
+        // The help page scrolls by lines, and wraps around after the last one
+        assert_eq!(menu.interact(Interaction::Action(Action::Help)), None);
+        assert_eq!(menu.help_page(), Some(0));
+        let first_page = draw(&mut menu);
+
+        menu.interact(Interaction::Navigation(Navigation::Next));
+        assert_eq!(menu.selected_value(), 1);
+        assert_ne!(draw(&mut menu), first_page);
+
+        menu.interact(Interaction::Navigation(Navigation::End));
+        menu.interact(Interaction::Navigation(Navigation::Next));
+        assert_eq!(draw(&mut menu), first_page);
+
+        // Actions close the page without activating the item
+        assert_eq!(menu.interact(Interaction::Action(Action::Select)), None);
+        assert_eq!(menu.help_page(), None);
+
+        // Items without help text don't open a page
+        menu.interact(Interaction::Navigation(Navigation::Next));
+        assert!(!menu.show_help());
+        assert_eq!(menu.help_page(), None);
+    }
+}
@@ -0,0 +1,189 @@
+use crate::interaction::{InputAdapter, InputAdapterSource, InputResult, InputState};
+
+/// Input of a [`Combined`] input adapter.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Either<A, B> {
+    /// Input for the first adapter.
+    Left(A),
+    /// Input for the second adapter.
+    Right(B),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct State<A, B> {
+    left: A,
+    right: B,
+    left_input: InputState,
+    right_input: InputState,
+}
+
+impl<A, B> Default for State<A, B>
+where
+    A: Default,
+    B: Default,
+{
+    fn default() -> Self {
+        Self {
+            left: Default::default(),
+            right: Default::default(),
+            left_input: InputState::Idle,
+            right_input: InputState::Idle,
+        }
+    }
+}
+
+/// Drive a menu using two different input adapters
+///
+/// Inputs are forwarded to the adapter they belong to. Both adapters keep their own state. If both
+/// adapters report an interaction in progress, the one closer to completion is displayed.
+#[derive(Clone, Copy)]
+pub struct Combined<A, B> {
+    pub left: A,
+    pub right: B,
+}
+
+impl<A, B, R> InputAdapterSource<R> for Combined<A, B>
+where
+    A: InputAdapterSource<R>,
+    B: InputAdapterSource<R>,
+{
+    type InputAdapter = CombinedAdapter<A::InputAdapter, B::InputAdapter>;
+
+    fn adapter(&self) -> Self::InputAdapter {
+        CombinedAdapter {
+            left: self.left.adapter(),
+            right: self.right.adapter(),
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct CombinedAdapter<A, B> {
+    left: A,
+    right: B,
+}
+
+impl<A, B, R> InputAdapter for CombinedAdapter<A, B>
+where
+    A: InputAdapter<Value = R>,
+    B: InputAdapter<Value = R>,
+{
+    type Input = Either<A::Input, B::Input>;
+    type Value = R;
+    type State = State<A::State, B::State>;
+
+    fn handle_input(
+        &self,
+        state: &mut Self::State,
+        action: Self::Input,
+    ) -> InputResult<Self::Value> {
+        let (result, last_input) = match action {
+            Either::Left(input) => (
+                self.left.handle_input(&mut state.left, input),
+                &mut state.left_input,
+            ),
+            Either::Right(input) => (
+                self.right.handle_input(&mut state.right, input),
+                &mut state.right_input,
+            ),
+        };
+
+        match result {
+            InputResult::StateUpdate(input_state) => {
+                *last_input = input_state;
+                InputResult::from(merge(state.left_input, state.right_input))
+            }
+            interaction => {
+                *last_input = InputState::Idle;
+                interaction
+            }
+        }
+    }
+
+    fn update(&self, state: &mut Self::State) {
+        self.left.update(&mut state.left);
+        self.right.update(&mut state.right);
+    }
+}
+
+fn merge(a: InputState, b: InputState) -> InputState {
+    match (a, b) {
+        (InputState::InProgress(a), InputState::InProgress(b)) => InputState::InProgress(a.max(b)),
+        (InputState::InProgress(a), _) | (_, InputState::InProgress(a)) => {
+            InputState::InProgress(a)
+        }
+        _ => InputState::Idle,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::interaction::{
+        combined::{Combined, Either},
+        programmed::Programmed,
+        single_touch::SingleTouch,
+        Action, InputAdapter, InputAdapterSource, InputResult, InputState, Interaction, Navigation,
+    };
+
+    fn single_touch<R>(max_time: u32) -> SingleTouch<R> {
+        SingleTouch::new(0, 0, max_time)
+    }
+
+    #[test]
+    fn progress_is_merged() {
+        let controller = Combined {
+            left: single_touch(4),
+            right: single_touch(8),
+        }
+        .adapter();
+
+        let expectations: [(Either<bool, bool>, InputResult<()>); 9] = [
+            (Either::Left(false), InputState::Idle.into()),
+            (Either::Right(false), InputState::Idle.into()),
+            (Either::Left(true), InputState::InProgress(63).into()),
+            (Either::Right(true), InputState::InProgress(63).into()),
+            (Either::Right(true), InputState::InProgress(63).into()),
+            (Either::Right(true), InputState::InProgress(95).into()),
+            (
+                Either::Left(false),
+                Interaction::Navigation(Navigation::Next).into(),
+            ),
+            (Either::Right(true), InputState::InProgress(127).into()),
+            (Either::Left(true), InputState::InProgress(127).into()),
+        ];
+
+        let mut state = Default::default();
+        for (sample, (input, expectation)) in expectations.into_iter().enumerate() {
+            let ret = controller.handle_input(&mut state, input);
+
+            assert_eq!(ret, expectation, "Mismatch at sample {}", sample);
+        }
+    }
+
+    #[test]
+    fn interactions_are_forwarded() {
+        let controller = Combined {
+            left: Programmed,
+            right: single_touch(4),
+        }
+        .adapter();
+
+        let mut state = Default::default();
+
+        assert_eq!(
+            controller.handle_input(
+                &mut state,
+                Either::Left(Interaction::Action(Action::Return(3)))
+            ),
+            Interaction::Action(Action::Return(3)).into()
+        );
+        assert_eq!(
+            controller.handle_input(&mut state, Either::Right(false)),
+            InputState::Idle.into()
+        );
+        assert_eq!(
+            controller.handle_input(&mut state, Either::Right(true)),
+            InputState::InProgress(63).into()
+        );
+    }
+}
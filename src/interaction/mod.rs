@@ -1,15 +1,21 @@
+pub mod combined;
 pub mod programmed;
+pub mod recorder;
 pub mod single_touch;
 
 #[cfg(feature = "simulator")]
 pub mod simulator;
 
+use embedded_graphics::prelude::Point;
+
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum Interaction<R> {
     /// Change the selection
     Navigation(Navigation),
     /// Return a value
     Action(Action<R>),
+    /// Select the item at the given display position, or activate it if it's already selected.
+    Click(Point),
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
@@ -18,6 +24,8 @@ pub enum Action<R> {
     Select,
     /// Return a value
     Return(R),
+    /// Display the help text of the currently selected item.
+    Help,
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
@@ -41,6 +49,23 @@ pub enum Navigation {
     End,
     /// Jump to the `usize`th item in the list, clamping at the beginning and end if necessary.
     JumpTo(usize),
+    /// Move left within the selected item, e.g. step its value backwards.
+    Left,
+    /// Move right within the selected item, e.g. step its value forwards.
+    Right,
+    /// Select the next item whose title starts with the given character, ignoring ASCII case.
+    JumpToLetter(char),
+    /// Display the previous tab of a tabbed menu, wrapping around to the last tab.
+    PreviousTab,
+    /// Display the next tab of a tabbed menu, wrapping around to the first tab.
+    NextTab,
+}
+
+/// Horizontal direction of movement within a menu item.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Direction {
+    Left,
+    Right,
 }
 
 impl Navigation {
@@ -52,6 +77,10 @@ impl Navigation {
         count: usize,
         selectable: impl Fn(usize) -> bool,
     ) -> usize {
+        if count == 0 {
+            return 0;
+        }
+
         // Clamp the selection to the range of selectable items.
         selected = selected.clamp(0, count - 1);
         let original = selected;
@@ -135,6 +164,12 @@ impl Navigation {
                     selected
                 }
             }
+            // Horizontal navigation is handled by the selected item, tabs by the menu.
+            Self::Left
+            | Self::Right
+            | Self::JumpToLetter(_)
+            | Self::PreviousTab
+            | Self::NextTab => selected,
         }
     }
 }
@@ -179,6 +214,9 @@ pub trait InputAdapter: Copy {
         state: &mut Self::State,
         action: Self::Input,
     ) -> InputResult<Self::Value>;
+
+    /// Called once every time the menu is updated.
+    fn update(&self, _state: &mut Self::State) {}
 }
 
 #[cfg(test)]
@@ -0,0 +1,605 @@
+//! Record menu inputs for later, deterministic replay.
+//!
+//! Wrap the input adapter of a menu in a [`Recorder`] to store every input passed to
+//! [`Menu::interact`] and every [`Menu::update`] call in a [`Recording`]. The recording can be
+//! serialized into a compact byte buffer, e.g. to attach it to a bug report, and replayed on a
+//! freshly built menu using [`Menu::replay`].
+//!
+//! [`Menu::interact`]: crate::Menu::interact
+//! [`Menu::update`]: crate::Menu::update
+//! [`Menu::replay`]: crate::Menu::replay
+
+use core::cell::{Cell, RefCell};
+
+use embedded_graphics::prelude::Point;
+
+use crate::interaction::{
+    combined::Either, Action, InputAdapter, InputAdapterSource, InputResult, Interaction,
+    Navigation,
+};
+
+const MAGIC: [u8; 2] = *b"MR";
+const VERSION: u8 = 2;
+
+const TAG_UPDATE: u8 = 0;
+const TAG_INPUT: u8 = 1;
+
+/// A single recorded event.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Event<I> {
+    /// An input passed to the menu.
+    Input(I),
+    /// A number of consecutive menu updates.
+    Update(u16),
+}
+
+/// Fixed capacity storage of recorded events.
+///
+/// Consecutive updates are stored as a single event. Once the recording is full, further events
+/// are dropped and [`Recording::overflowed`] returns `true`.
+pub struct Recording<I, const N: usize>
+where
+    I: Copy,
+{
+    events: RefCell<[Event<I>; N]>,
+    len: Cell<usize>,
+    overflowed: Cell<bool>,
+}
+
+impl<I, const N: usize> Default for Recording<I, N>
+where
+    I: Copy,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<I, const N: usize> Recording<I, N>
+where
+    I: Copy,
+{
+    /// Creates an empty recording.
+    pub const fn new() -> Self {
+        Self {
+            events: RefCell::new([Event::Update(0); N]),
+            len: Cell::new(0),
+            overflowed: Cell::new(false),
+        }
+    }
+
+    /// Returns the number of stored events.
+    pub fn len(&self) -> usize {
+        self.len.get()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns `true` if events were dropped because the recording was full.
+    pub fn overflowed(&self) -> bool {
+        self.overflowed.get()
+    }
+
+    /// Removes all recorded events.
+    pub fn clear(&self) {
+        self.len.set(0);
+        self.overflowed.set(false);
+    }
+
+    /// Returns the `nth` recorded event.
+    pub fn get(&self, nth: usize) -> Option<Event<I>> {
+        if nth < self.len() {
+            Some(self.events.borrow()[nth])
+        } else {
+            None
+        }
+    }
+
+    /// Returns an iterator over the recorded events.
+    pub fn iter(&self) -> impl Iterator<Item = Event<I>> + '_ {
+        (0..self.len()).filter_map(|nth| self.get(nth))
+    }
+
+    /// Appends an event to the recording.
+    pub fn record(&self, event: Event<I>) {
+        let len = self.len();
+        let mut events = self.events.borrow_mut();
+
+        if let Event::Update(count) = event {
+            if let Some(Event::Update(last)) = len.checked_sub(1).map(|last| &mut events[last]) {
+                if let Some(sum) = last.checked_add(count) {
+                    *last = sum;
+                    return;
+                }
+            }
+        }
+
+        if len < N {
+            events[len] = event;
+            self.len.set(len + 1);
+        } else {
+            self.overflowed.set(true);
+        }
+    }
+
+    /// Serializes the recording into `buffer`.
+    ///
+    /// Returns the number of bytes written, or `None` if the buffer is too small.
+    pub fn serialize(&self, buffer: &mut [u8]) -> Option<usize>
+    where
+        I: InputCodec,
+    {
+        let mut writer = Writer::new(buffer);
+
+        writer.write(&MAGIC)?;
+        writer.write(&[VERSION])?;
+
+        for event in self.iter() {
+            match event {
+                Event::Update(count) => {
+                    writer.write(&[TAG_UPDATE])?;
+                    writer.write(&count.to_le_bytes())?;
+                }
+                Event::Input(input) => {
+                    writer.write(&[TAG_INPUT])?;
+                    let len = input.encode(writer.remaining())?;
+                    writer.advance(len);
+                }
+            }
+        }
+
+        Some(writer.position)
+    }
+
+    /// Deserializes a recording created by [`Recording::serialize`].
+    ///
+    /// Returns `None` if the data is malformed, was created by an incompatible version, or
+    /// doesn't fit into the recording.
+    pub fn deserialize(mut bytes: &[u8]) -> Option<Self>
+    where
+        I: InputCodec,
+    {
+        let recording = Self::new();
+
+        let (header, rest) = split(bytes, 3)?;
+        if header[..2] != MAGIC || header[2] != VERSION {
+            return None;
+        }
+        bytes = rest;
+
+        while let Some((&tag, rest)) = bytes.split_first() {
+            let event = match tag {
+                TAG_UPDATE => {
+                    let (count, rest) = split(rest, 2)?;
+                    bytes = rest;
+                    Event::Update(u16::from_le_bytes([count[0], count[1]]))
+                }
+                TAG_INPUT => {
+                    let (input, len) = I::decode(rest)?;
+                    bytes = rest.get(len..)?;
+                    Event::Input(input)
+                }
+                _ => return None,
+            };
+
+            if recording.len() == N {
+                return None;
+            }
+            recording.record(event);
+        }
+
+        Some(recording)
+    }
+}
+
+/// Inputs that can be stored in a serialized [`Recording`].
+pub trait InputCodec: Sized {
+    /// Encodes the input into `buffer`.
+    ///
+    /// Returns the number of bytes written, or `None` if the buffer is too small.
+    fn encode(&self, buffer: &mut [u8]) -> Option<usize>;
+
+    /// Decodes an input from the beginning of `buffer`.
+    ///
+    /// Returns the input and the number of bytes read, or `None` if the data is malformed.
+    fn decode(buffer: &[u8]) -> Option<(Self, usize)>;
+}
+
+impl InputCodec for () {
+    fn encode(&self, _buffer: &mut [u8]) -> Option<usize> {
+        Some(0)
+    }
+
+    fn decode(_buffer: &[u8]) -> Option<(Self, usize)> {
+        Some(((), 0))
+    }
+}
+
+impl InputCodec for bool {
+    fn encode(&self, buffer: &mut [u8]) -> Option<usize> {
+        Writer::new(buffer).write(&[*self as u8])
+    }
+
+    fn decode(buffer: &[u8]) -> Option<(Self, usize)> {
+        match buffer.first()? {
+            0 => Some((false, 1)),
+            1 => Some((true, 1)),
+            _ => None,
+        }
+    }
+}
+
+impl InputCodec for u8 {
+    fn encode(&self, buffer: &mut [u8]) -> Option<usize> {
+        Writer::new(buffer).write(&[*self])
+    }
+
+    fn decode(buffer: &[u8]) -> Option<(Self, usize)> {
+        buffer.first().map(|byte| (*byte, 1))
+    }
+}
+
+impl<A, B> InputCodec for Either<A, B>
+where
+    A: InputCodec,
+    B: InputCodec,
+{
+    fn encode(&self, buffer: &mut [u8]) -> Option<usize> {
+        let (tag, rest) = buffer.split_first_mut()?;
+        let len = match self {
+            Either::Left(input) => {
+                *tag = 0;
+                input.encode(rest)?
+            }
+            Either::Right(input) => {
+                *tag = 1;
+                input.encode(rest)?
+            }
+        };
+
+        Some(len + 1)
+    }
+
+    fn decode(buffer: &[u8]) -> Option<(Self, usize)> {
+        let (tag, rest) = buffer.split_first()?;
+        let (input, len) = match tag {
+            0 => A::decode(rest).map(|(input, len)| (Either::Left(input), len))?,
+            1 => B::decode(rest).map(|(input, len)| (Either::Right(input), len))?,
+            _ => return None,
+        };
+
+        Some((input, len + 1))
+    }
+}
+
+impl<R> InputCodec for Interaction<R>
+where
+    R: InputCodec,
+{
+    fn encode(&self, buffer: &mut [u8]) -> Option<usize> {
+        let mut writer = Writer::new(buffer);
+
+        match *self {
+            Interaction::Navigation(navigation) => {
+                let (tag, count) = match navigation {
+                    Navigation::Previous => (0, None),
+                    Navigation::Next => (1, None),
+                    Navigation::ForwardWrapping(n) => (2, Some(n)),
+                    Navigation::Forward(n) => (3, Some(n)),
+                    Navigation::BackwardWrapping(n) => (4, Some(n)),
+                    Navigation::Backward(n) => (5, Some(n)),
+                    Navigation::Beginning => (6, None),
+                    Navigation::End => (7, None),
+                    Navigation::JumpTo(n) => (8, Some(n)),
+                    Navigation::Left => (9, None),
+                    Navigation::Right => (10, None),
+                    Navigation::JumpToLetter(letter) => (14, Some(letter as usize)),
+                    Navigation::PreviousTab => (15, None),
+                    Navigation::NextTab => (16, None),
+                };
+                writer.write(&[tag])?;
+                if let Some(count) = count {
+                    // Counts that don't fit are saturated, which is only lossless for `usize::MAX`.
+                    let count = u32::try_from(count).unwrap_or(u32::MAX);
+                    writer.write(&count.to_le_bytes())?;
+                }
+            }
+            Interaction::Action(Action::Select) => {
+                writer.write(&[11])?;
+            }
+            Interaction::Action(Action::Return(ref value)) => {
+                writer.write(&[12])?;
+                let len = value.encode(writer.remaining())?;
+                writer.advance(len);
+            }
+            Interaction::Click(point) => {
+                writer.write(&[13])?;
+                writer.write(&point.x.to_le_bytes())?;
+                writer.write(&point.y.to_le_bytes())?;
+            }
+            Interaction::Action(Action::Help) => {
+                writer.write(&[17])?;
+            }
+        }
+
+        Some(writer.position)
+    }
+
+    fn decode(buffer: &[u8]) -> Option<(Self, usize)> {
+        let (&tag, rest) = buffer.split_first()?;
+
+        let read_u32 = |bytes: &[u8]| -> Option<[u8; 4]> { bytes.get(..4)?.try_into().ok() };
+        let read_count = || {
+            read_u32(rest).map(|bytes| match u32::from_le_bytes(bytes) {
+                u32::MAX => usize::MAX,
+                count => count as usize,
+            })
+        };
+
+        let navigation = |navigation| Some((Interaction::Navigation(navigation), 1));
+        let counted = |navigation: fn(usize) -> Navigation| {
+            read_count().map(|count| (Interaction::Navigation(navigation(count)), 5))
+        };
+
+        match tag {
+            0 => navigation(Navigation::Previous),
+            1 => navigation(Navigation::Next),
+            2 => counted(Navigation::ForwardWrapping),
+            3 => counted(Navigation::Forward),
+            4 => counted(Navigation::BackwardWrapping),
+            5 => counted(Navigation::Backward),
+            6 => navigation(Navigation::Beginning),
+            7 => navigation(Navigation::End),
+            8 => counted(Navigation::JumpTo),
+            9 => navigation(Navigation::Left),
+            10 => navigation(Navigation::Right),
+            11 => Some((Interaction::Action(Action::Select), 1)),
+            12 => R::decode(rest)
+                .map(|(value, len)| (Interaction::Action(Action::Return(value)), len + 1)),
+            13 => {
+                let x = i32::from_le_bytes(read_u32(rest)?);
+                let y = i32::from_le_bytes(read_u32(rest.get(4..)?)?);
+                Some((Interaction::Click(Point::new(x, y)), 9))
+            }
+            14 => read_count()
+                .and_then(|letter| char::from_u32(letter as u32))
+                .map(|letter| (Interaction::Navigation(Navigation::JumpToLetter(letter)), 5)),
+            15 => navigation(Navigation::PreviousTab),
+            16 => navigation(Navigation::NextTab),
+            17 => Some((Interaction::Action(Action::Help), 1)),
+            _ => None,
+        }
+    }
+}
+
+struct Writer<'b> {
+    buffer: &'b mut [u8],
+    position: usize,
+}
+
+impl<'b> Writer<'b> {
+    fn new(buffer: &'b mut [u8]) -> Self {
+        Self {
+            buffer,
+            position: 0,
+        }
+    }
+
+    fn remaining(&mut self) -> &mut [u8] {
+        &mut self.buffer[self.position..]
+    }
+
+    fn advance(&mut self, len: usize) {
+        self.position += len;
+    }
+
+    fn write(&mut self, bytes: &[u8]) -> Option<usize> {
+        self.remaining()
+            .get_mut(..bytes.len())?
+            .copy_from_slice(bytes);
+        self.advance(bytes.len());
+
+        Some(self.position)
+    }
+}
+
+fn split(bytes: &[u8], at: usize) -> Option<(&[u8], &[u8])> {
+    if bytes.len() < at {
+        None
+    } else {
+        Some(bytes.split_at(at))
+    }
+}
+
+/// Records the inputs of the wrapped input adapter
+///
+/// Every input and every menu update is appended to `recording`. The wrapped adapter handles
+/// inputs as if it was used directly.
+#[derive(Clone, Copy)]
+pub struct Recorder<'a, IT, I, const N: usize>
+where
+    I: Copy,
+{
+    pub adapter: IT,
+    pub recording: &'a Recording<I, N>,
+}
+
+impl<'a, IT, I, R, const N: usize> InputAdapterSource<R> for Recorder<'a, IT, I, N>
+where
+    IT: InputAdapterSource<R>,
+    IT::InputAdapter: InputAdapter<Input = I>,
+    I: Copy,
+{
+    type InputAdapter = RecorderAdapter<'a, IT::InputAdapter, I, N>;
+
+    fn adapter(&self) -> Self::InputAdapter {
+        RecorderAdapter {
+            adapter: self.adapter.adapter(),
+            recording: self.recording,
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct RecorderAdapter<'a, A, I, const N: usize>
+where
+    I: Copy,
+{
+    adapter: A,
+    recording: &'a Recording<I, N>,
+}
+
+impl<'a, A, I, const N: usize> InputAdapter for RecorderAdapter<'a, A, I, N>
+where
+    A: InputAdapter<Input = I>,
+    I: Copy,
+{
+    type Input = I;
+    type Value = A::Value;
+    type State = A::State;
+
+    fn handle_input(
+        &self,
+        state: &mut Self::State,
+        action: Self::Input,
+    ) -> InputResult<Self::Value> {
+        self.recording.record(Event::Input(action));
+        self.adapter.handle_input(state, action)
+    }
+
+    fn update(&self, state: &mut Self::State) {
+        self.recording.record(Event::Update(1));
+        self.adapter.update(state);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use embedded_graphics::{mock_display::MockDisplay, pixelcolor::BinaryColor};
+
+    use crate::{
+        interaction::{
+            recorder::{Event, Recorder, Recording},
+            single_touch::SingleTouch,
+            Action, Interaction, Navigation,
+        },
+        items::MenuItem,
+        Menu, MenuStyle,
+    };
+
+    #[test]
+    fn events_are_stored_compactly() {
+        let recording = Recording::<Interaction<u8>, 4>::new();
+
+        recording.record(Event::Update(1));
+        recording.record(Event::Update(1));
+        recording.record(Event::Input(Interaction::Navigation(Navigation::Next)));
+        recording.record(Event::Update(u16::MAX));
+        recording.record(Event::Update(2));
+        recording.record(Event::Input(Interaction::Action(Action::Select)));
+
+        assert!(recording.overflowed());
+        assert_eq!(
+            recording.iter().collect::<Vec<_>>(),
+            [
+                Event::Update(2),
+                Event::Input(Interaction::Navigation(Navigation::Next)),
+                Event::Update(u16::MAX),
+                Event::Update(2),
+            ]
+        );
+    }
+
+    #[test]
+    fn interactions_survive_serialization() {
+        let inputs = [
+            Interaction::Navigation(Navigation::Previous),
+            Interaction::Navigation(Navigation::Forward(3)),
+            Interaction::Navigation(Navigation::JumpTo(usize::MAX)),
+            Interaction::Navigation(Navigation::Right),
+            Interaction::Navigation(Navigation::JumpToLetter('ő')),
+            Interaction::Navigation(Navigation::PreviousTab),
+            Interaction::Navigation(Navigation::NextTab),
+            Interaction::Action(Action::Select),
+            Interaction::Action(Action::Return(true)),
+            Interaction::Action(Action::Help),
+            Interaction::Click(embedded_graphics::prelude::Point::new(-1, 20)),
+        ];
+
+        let recording = Recording::<Interaction<bool>, 12>::new();
+        for input in inputs {
+            recording.record(Event::Input(input));
+        }
+        recording.record(Event::Update(300));
+
+        let mut buffer = [0; 64];
+        let len = recording.serialize(&mut buffer).unwrap();
+        assert!(recording.serialize(&mut buffer[..len - 1]).is_none());
+
+        let restored = Recording::<Interaction<bool>, 12>::deserialize(&buffer[..len]).unwrap();
+        assert!(recording.iter().eq(restored.iter()));
+
+        assert!(Recording::<Interaction<bool>, 11>::deserialize(&buffer[..len]).is_none());
+        assert!(Recording::<Interaction<bool>, 12>::deserialize(&buffer[..len - 1]).is_none());
+        assert!(Recording::<Interaction<bool>, 12>::deserialize(&buffer[1..len]).is_none());
+
+        // Recordings of other versions are rejected
+        buffer[2] = 1;
+        assert!(Recording::<Interaction<bool>, 12>::deserialize(&buffer[..len]).is_none());
+    }
+
+    #[test]
+    fn replay_is_deterministic() {
+        let input = SingleTouch::new(1, 1, 5);
+        let display = MockDisplay::<BinaryColor>::new();
+
+        let build = |recording| {
+            Menu::with_style(
+                "Menu",
+                MenuStyle::default().with_input_adapter(Recorder {
+                    adapter: input,
+                    recording,
+                }),
+            )
+            .add_item("Foo", false, |b| b as u8)
+            .add_menu_item(MenuItem::new("Bar", ()).with_value_converter(|_| 7))
+            .add_item("Baz", false, |b| b as u8 + 2)
+            .build()
+        };
+
+        let recording = Recording::<bool, 256>::new();
+        let mut menu = build(&recording);
+
+        let mut results = Vec::new();
+        for press in [0, 2, 7, 3, 0, 6, 2] {
+            for sample in 0..10 {
+                results.push(menu.interact(sample < press));
+                menu.update(&display);
+            }
+        }
+
+        assert!(!recording.overflowed());
+
+        let mut buffer = [0; 512];
+        let len = recording.serialize(&mut buffer).unwrap();
+        let restored = Recording::<bool, 256>::deserialize(&buffer[..len]).unwrap();
+
+        let replay_recording = Recording::<bool, 256>::new();
+        let mut replayed = build(&replay_recording);
+
+        let replayed_results = restored
+            .iter()
+            .filter_map(|event| {
+                let result = replayed.replay(event, &display);
+                matches!(event, Event::Input(_)).then_some(result)
+            })
+            .collect::<Vec<_>>();
+
+        assert!(results.iter().any(Option::is_some));
+        assert_eq!(results, replayed_results);
+        assert_eq!(menu.state(), replayed.state());
+        assert!(recording.iter().eq(replay_recording.iter()));
+    }
+}
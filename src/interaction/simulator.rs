@@ -1,10 +1,91 @@
-use embedded_graphics_simulator::{sdl2::Keycode, SimulatorEvent};
+use embedded_graphics_simulator::{
+    sdl2::{Keycode, MouseButton, MouseWheelDirection},
+    SimulatorEvent,
+};
 
-use crate::interaction::{
-    Action, InputAdapter, InputAdapterSource, InputResult, InputState, Interaction, Navigation,
+use crate::{
+    interaction::{
+        Action, InputAdapter, InputAdapterSource, InputResult, InputState, Interaction, Navigation,
+    },
+    selection_indicator::style::interpolate,
 };
 
+/// Menu operation triggered by a key.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Key {
+    /// Select the previous item.
+    Previous,
+    /// Select the next item.
+    Next,
+    /// Move the selection backwards by `page_size` items.
+    PageUp,
+    /// Move the selection forward by `page_size` items.
+    PageDown,
+    /// Select the first item.
+    Beginning,
+    /// Select the last item.
+    End,
+    /// Step the selected item's value backwards.
+    Left,
+    /// Step the selected item's value forwards.
+    Right,
+    /// Activate the selected item.
+    Select,
+    /// Return `esc_value`.
+    Back,
+    /// Display the help text of the selected item.
+    Help,
+}
+
+impl Key {
+    fn interaction<R>(self, page_size: usize, esc_value: R) -> Interaction<R> {
+        match self {
+            Key::Previous => Interaction::Navigation(Navigation::Previous),
+            Key::Next => Interaction::Navigation(Navigation::Next),
+            Key::PageUp => Interaction::Navigation(Navigation::Backward(page_size)),
+            Key::PageDown => Interaction::Navigation(Navigation::Forward(page_size)),
+            Key::Beginning => Interaction::Navigation(Navigation::Beginning),
+            Key::End => Interaction::Navigation(Navigation::End),
+            Key::Left => Interaction::Navigation(Navigation::Left),
+            Key::Right => Interaction::Navigation(Navigation::Right),
+            Key::Select => Interaction::Action(Action::Select),
+            Key::Back => Interaction::Action(Action::Return(esc_value)),
+            Key::Help => Interaction::Action(Action::Help),
+        }
+    }
+
+    /// Navigation keys repeat while held, other keys only act when released.
+    fn repeats(self) -> bool {
+        !matches!(self, Key::Select | Key::Back | Key::Help)
+    }
+}
+
+/// The default key bindings of the [`Simulator`] input adapter.
+pub const DEFAULT_KEYMAP: &[(Keycode, Key)] = &[
+    (Keycode::Up, Key::Previous),
+    (Keycode::Down, Key::Next),
+    (Keycode::PageUp, Key::PageUp),
+    (Keycode::PageDown, Key::PageDown),
+    (Keycode::Home, Key::Beginning),
+    (Keycode::End, Key::End),
+    (Keycode::Left, Key::Left),
+    (Keycode::Right, Key::Right),
+    (Keycode::Return, Key::Select),
+    (Keycode::Escape, Key::Back),
+    (Keycode::Backspace, Key::Back),
+    (Keycode::F1, Key::Help),
+];
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct State {
+    held: Option<Keycode>,
+    repeats: u32,
+}
+
 /// Input adapter to work with the embedded-graphics simulator
+///
+/// Mouse wheel scrolls the menu, clicking an item selects it, clicking the selected item activates
+/// it.
 #[derive(Clone, Copy)]
 pub struct Simulator<R>
 where
@@ -13,6 +94,40 @@ where
     /// Number of menu items to skip when pressing page up or page down.
     pub page_size: usize,
     pub esc_value: R,
+    keymap: &'static [(Keycode, Key)],
+    hold_time: u32,
+}
+
+impl<R> Simulator<R>
+where
+    R: Copy,
+{
+    /// Creates a simulator input adapter with the [default keymap](DEFAULT_KEYMAP).
+    pub const fn new(page_size: usize, esc_value: R) -> Self {
+        Self {
+            page_size,
+            esc_value,
+            keymap: DEFAULT_KEYMAP,
+            hold_time: 10,
+        }
+    }
+
+    /// Replaces the key bindings. If a key is bound multiple times, the first binding is used.
+    pub const fn with_keymap(self, keymap: &'static [(Keycode, Key)]) -> Self {
+        Self { keymap, ..self }
+    }
+
+    /// Sets the number of key repeat events until a held key is displayed as fully pressed.
+    pub const fn with_hold_time(self, hold_time: u32) -> Self {
+        Self { hold_time, ..self }
+    }
+
+    fn key(&self, keycode: Keycode) -> Option<Key> {
+        self.keymap
+            .iter()
+            .find(|(bound, _)| *bound == keycode)
+            .map(|(_, key)| *key)
+    }
 }
 
 impl<R> InputAdapterSource<R> for Simulator<R>
@@ -32,33 +147,81 @@ where
 {
     type Input = SimulatorEvent;
     type Value = R;
-    type State = ();
+    type State = State;
 
     fn handle_input(
         &self,
-        _state: &mut Self::State,
+        state: &mut Self::State,
         action: Self::Input,
     ) -> InputResult<Self::Value> {
         match action {
-            SimulatorEvent::KeyDown { repeat: false, .. } => InputResult::from(InputState::Idle),
-            SimulatorEvent::KeyDown { repeat: true, .. } => {
-                InputResult::from(InputState::InProgress(255))
+            SimulatorEvent::KeyDown {
+                keycode,
+                repeat: false,
+                ..
+            } => {
+                state.held = Some(keycode);
+                state.repeats = 0;
+                InputResult::from(InputState::Idle)
             }
-            SimulatorEvent::KeyUp { keycode, .. } => match keycode {
-                Keycode::Return => InputResult::from(Interaction::Action(Action::Select)),
-                Keycode::Up => InputResult::from(Interaction::Navigation(Navigation::Previous)),
-                Keycode::Down => InputResult::from(Interaction::Navigation(Navigation::Next)),
-                Keycode::PageDown => {
-                    InputResult::from(Interaction::Navigation(Navigation::Forward(self.page_size)))
+            SimulatorEvent::KeyDown {
+                keycode,
+                repeat: true,
+                ..
+            } => {
+                let Some(key) = self.key(keycode) else {
+                    return InputResult::from(InputState::Idle);
+                };
+
+                state.repeats = state.repeats.saturating_add(1);
+
+                if key.repeats() {
+                    InputResult::from(key.interaction(self.page_size, self.esc_value))
+                } else {
+                    InputResult::from(InputState::InProgress(interpolate(
+                        state.repeats.min(self.hold_time),
+                        0,
+                        self.hold_time,
+                        0,
+                        255,
+                    ) as u8))
                 }
-                Keycode::PageUp => InputResult::from(Interaction::Navigation(
-                    Navigation::Backward(self.page_size),
-                )),
-                Keycode::Escape => {
-                    InputResult::from(Interaction::Action(Action::Return(self.esc_value)))
+            }
+            SimulatorEvent::KeyUp { keycode, .. } => {
+                let repeated = state.held == Some(keycode) && state.repeats > 0;
+                *state = State::default();
+
+                match self.key(keycode) {
+                    // Repeating keys have already acted while held.
+                    Some(key) if key.repeats() && repeated => InputResult::from(InputState::Idle),
+                    Some(key) => InputResult::from(key.interaction(self.page_size, self.esc_value)),
+                    None => InputResult::from(InputState::Idle),
                 }
-                _ => InputResult::from(InputState::Idle),
-            },
+            }
+            SimulatorEvent::MouseWheel {
+                scroll_delta,
+                direction,
+            } => {
+                let delta = if direction == MouseWheelDirection::Flipped {
+                    -scroll_delta.y
+                } else {
+                    scroll_delta.y
+                };
+
+                let navigation = if delta > 0 {
+                    Navigation::Backward(delta as usize)
+                } else if delta < 0 {
+                    Navigation::Forward(delta.unsigned_abs() as usize)
+                } else {
+                    return InputResult::from(InputState::Idle);
+                };
+
+                InputResult::from(Interaction::Navigation(navigation))
+            }
+            SimulatorEvent::MouseButtonUp {
+                mouse_btn: MouseButton::Left,
+                point,
+            } => InputResult::from(Interaction::Click(point)),
             SimulatorEvent::Quit => {
                 InputResult::from(Interaction::Action(Action::Return(self.esc_value)))
             }
@@ -1,5 +1,3 @@
-use core::marker::PhantomData;
-
 use crate::{
     interaction::{
         Action, InputAdapter, InputAdapterSource, InputResult, InputState, Interaction, Navigation,
@@ -7,19 +5,31 @@ use crate::{
     selection_indicator::style::interpolate,
 };
 
-#[derive(Default, Debug, Clone, Copy)]
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
 pub struct State {
     interaction_time: u32,
     was_released: bool,
     repeated: bool,
+    /// Update periods since a short press was released while waiting for a second tap.
+    release_time: u32,
+    /// A short press was released and may turn out to be the first half of a double tap.
+    tap_pending: bool,
+    /// The current press started while a tap was pending.
+    second_press: bool,
 }
 
 /// Single touch navigation in hierarchical lists
 ///
 /// Short press: select next item
 /// Long press: activate current item
+///
+/// Optional gestures:
+/// Double tap: select previous item
+/// Very long press: return a value, e.g. to leave the menu
+/// Longer press: open the help text of the current item
+/// Tap, then hold: keep selecting the next item while held
 #[derive(Clone, Copy)]
-pub struct SingleTouch {
+pub struct SingleTouch<R> {
     /// Does not display short presses on the selection indicator.
     pub ignore_time: u32,
 
@@ -28,17 +38,109 @@ pub struct SingleTouch {
 
     /// Detects long presses after this many update periods.
     pub max_time: u32,
+
+    double_tap_time: Option<u32>,
+    back: Option<(u32, R)>,
+    help: Option<u32>,
+    auto_advance_time: Option<u32>,
+}
+
+impl<R> SingleTouch<R> {
+    /// Creates a single touch input that ignores touches shorter than `debounce_time` and detects
+    /// long presses after `max_time` update periods. Presses shorter than `ignore_time` are not
+    /// displayed on the selection indicator.
+    pub const fn new(ignore_time: u32, debounce_time: u32, max_time: u32) -> Self {
+        Self {
+            ignore_time,
+            debounce_time,
+            max_time,
+            double_tap_time: None,
+            back: None,
+            help: None,
+            auto_advance_time: None,
+        }
+    }
+
+    /// Selects the previous item on a double tap.
+    ///
+    /// A double tap is detected if the second press starts within `time` update periods after the
+    /// first one is released. Single taps are only reported after this time has passed.
+    pub fn with_double_tap(self, time: u32) -> Self {
+        Self {
+            double_tap_time: Some(time),
+            ..self
+        }
+    }
+
+    /// Returns `value` if the input is held for `time` update periods, which must be longer than
+    /// a long press.
+    ///
+    /// Long presses then activate the current item on release instead of repeatedly while held.
+    pub fn with_back(self, time: u32, value: R) -> Self {
+        Self {
+            back: Some((time, value)),
+            ..self
+        }
+    }
+
+    /// Opens the help text of the current item if the input is held for `time` update periods,
+    /// which must be longer than a long press.
+    ///
+    /// If [`with_back`](Self::with_back) is also used, `time` must be shorter than its time, and
+    /// the help text is opened when the input is released.
+    pub fn with_help(self, time: u32) -> Self {
+        Self {
+            help: Some(time),
+            ..self
+        }
+    }
+
+    /// Selects the next item every `period` update periods while the second press of a double
+    /// tap is held longer than a long press. Requires [`with_double_tap`](Self::with_double_tap).
+    pub fn with_auto_advance(self, period: u32) -> Self {
+        Self {
+            auto_advance_time: Some(period),
+            ..self
+        }
+    }
 }
 
-impl<R> InputAdapterSource<R> for SingleTouch {
+impl<R> InputAdapterSource<R> for SingleTouch<R>
+where
+    R: Copy,
+{
     type InputAdapter = SingleTouchAdapter<R>;
 
     fn adapter(&self) -> Self::InputAdapter {
+        let back_time = self.back.map(|(back_time, _)| back_time);
+        if let Some(back_time) = back_time {
+            debug_assert!(
+                back_time > self.max_time,
+                "The back time must be longer than a long press"
+            );
+        }
+        if let Some(help) = self.help {
+            debug_assert!(
+                help > self.max_time && !matches!(back_time, Some(back_time) if back_time <= help),
+                "The help time must be longer than a long press and shorter than the back time"
+            );
+        }
+        debug_assert!(
+            self.auto_advance_time.is_none() || self.double_tap_time.is_some(),
+            "Auto advance requires double tap detection"
+        );
+
         SingleTouchAdapter {
             ignore_time: self.ignore_time,
             debounce_time: self.debounce_time,
             max_time: self.max_time,
-            marker: PhantomData,
+            double_tap_time: self.double_tap_time,
+            back: self.back,
+            help: self.help.filter(|&help| {
+                help > self.max_time
+                    && !matches!(self.back, Some((back_time, _)) if back_time <= help)
+            }),
+            auto_advance_time: self.auto_advance_time,
         }
     }
 }
@@ -51,18 +153,56 @@ pub struct SingleTouchAdapter<R> {
     ignore_time: u32,
     debounce_time: u32,
     max_time: u32,
-    marker: PhantomData<R>,
+    double_tap_time: Option<u32>,
+    back: Option<(u32, R)>,
+    help: Option<u32>,
+    auto_advance_time: Option<u32>,
+}
+
+impl<R> SingleTouchAdapter<R>
+where
+    R: Copy,
+{
+    /// Returns the start and the end of the gesture that a press held for `time` update periods
+    /// progresses towards, if the press is longer than a long press.
+    fn hold_stage(&self, time: u32) -> Option<(u32, u32)> {
+        let mut thresholds = [self.help, self.back.map(|(back_time, _)| back_time)]
+            .into_iter()
+            .flatten();
+
+        let mut start = self.max_time;
+        thresholds.find_map(|end| {
+            let stage = (time < end).then_some((start, end));
+            start = end;
+            stage
+        })
+    }
+
+    /// Returns the action of the longest press, which is reported while the input is held.
+    fn very_long_press(&self) -> Option<Action<R>> {
+        match (self.back, self.help) {
+            (Some((_, value)), _) => Some(Action::Return(value)),
+            (None, Some(_)) => Some(Action::Help),
+            (None, None) => None,
+        }
+    }
 }
 
-impl<R> Clone for SingleTouchAdapter<R> {
+impl<R> Clone for SingleTouchAdapter<R>
+where
+    R: Copy,
+{
     fn clone(&self) -> Self {
         *self
     }
 }
 
-impl<R> Copy for SingleTouchAdapter<R> {}
+impl<R> Copy for SingleTouchAdapter<R> where R: Copy {}
 
-impl<R> InputAdapter for SingleTouchAdapter<R> {
+impl<R> InputAdapter for SingleTouchAdapter<R>
+where
+    R: Copy,
+{
     type Input = bool;
     type Value = R;
     type State = State;
@@ -80,7 +220,27 @@ impl<R> InputAdapter for SingleTouchAdapter<R> {
         }
 
         if action {
+            if state.interaction_time == 0 && state.tap_pending {
+                state.tap_pending = false;
+                state.second_press = true;
+            }
+
             state.interaction_time = state.interaction_time.saturating_add(1);
+
+            if state.second_press && state.interaction_time >= self.max_time {
+                if let Some(period) = self.auto_advance_time {
+                    state.repeated = true;
+                    let held = state.interaction_time - self.max_time;
+                    // `is_multiple_of` is not available on our MSRV.
+                    #[allow(clippy::manual_is_multiple_of)]
+                    return if held % period.max(1) == 0 {
+                        InputResult::from(Interaction::Navigation(Navigation::Next))
+                    } else {
+                        InputResult::from(InputState::Idle)
+                    };
+                }
+            }
+
             if state.interaction_time <= self.ignore_time && !state.repeated {
                 InputResult::from(InputState::Idle)
             } else if state.interaction_time < self.max_time {
@@ -92,6 +252,20 @@ impl<R> InputAdapter for SingleTouchAdapter<R> {
                     0,
                     255,
                 ) as u8))
+            } else if let Some((start, end)) = self.hold_stage(state.interaction_time) {
+                InputResult::from(InputState::InProgress(interpolate(
+                    state.interaction_time - start,
+                    0,
+                    end - start,
+                    0,
+                    255,
+                ) as u8))
+            } else if let Some(action) = self.very_long_press() {
+                // Ignore the input until it is released.
+                state.interaction_time = 0;
+                state.second_press = false;
+                state.was_released = false;
+                InputResult::from(Interaction::Action(action))
             } else {
                 state.repeated = true;
                 state.interaction_time = 0;
@@ -99,13 +273,44 @@ impl<R> InputAdapter for SingleTouchAdapter<R> {
             }
         } else {
             let time = core::mem::replace(&mut state.interaction_time, 0);
+            let second_press = core::mem::replace(&mut state.second_press, false);
 
-            if self.debounce_time < time && time < self.max_time && !state.repeated {
-                InputResult::from(Interaction::Navigation(Navigation::Next))
-            } else {
+            if state.repeated {
                 // Already interacted before releasing, ignore and reset.
                 state.repeated = false;
                 InputResult::from(InputState::Idle)
+            } else if self.help.is_some_and(|help| time >= help) {
+                // Only reachable if very long presses return a value.
+                InputResult::from(Interaction::Action(Action::Help))
+            } else if time >= self.max_time {
+                // Only reachable if long presses are reported on release.
+                InputResult::from(Interaction::Action(Action::Select))
+            } else if self.debounce_time < time {
+                if second_press {
+                    InputResult::from(Interaction::Navigation(Navigation::Previous))
+                } else if self.double_tap_time.is_some() {
+                    state.tap_pending = true;
+                    state.release_time = 0;
+                    InputResult::from(InputState::Idle)
+                } else {
+                    InputResult::from(Interaction::Navigation(Navigation::Next))
+                }
+            } else {
+                // A bouncing second press does not cancel the pending tap.
+                state.tap_pending |= second_press;
+
+                match self.double_tap_time {
+                    Some(double_tap_time) if state.tap_pending => {
+                        state.release_time += 1;
+                        if state.release_time >= double_tap_time {
+                            state.tap_pending = false;
+                            InputResult::from(Interaction::Navigation(Navigation::Next))
+                        } else {
+                            InputResult::from(InputState::Idle)
+                        }
+                    }
+                    _ => InputResult::from(InputState::Idle),
+                }
             }
         }
     }
@@ -113,6 +318,8 @@ impl<R> InputAdapter for SingleTouchAdapter<R> {
 
 #[cfg(test)]
 mod test {
+    use core::fmt::Debug;
+
     use crate::interaction::{
         single_touch::SingleTouch, Action, InputAdapter, InputAdapterSource, InputResult,
         InputState, Interaction, Navigation,
@@ -121,12 +328,7 @@ mod test {
     #[test]
     fn test_interaction() {
         // ignore 1 long pulses, accept 2-4 as short press, 5- as long press
-        let controller = SingleTouch {
-            ignore_time: 1,
-            debounce_time: 1,
-            max_time: 5,
-        }
-        .adapter();
+        let controller = SingleTouch::new(1, 1, 5).adapter();
 
         let expectations: [&[(bool, InputResult<()>)]; 6] = [
             &[
@@ -189,6 +391,257 @@ mod test {
             ],
         ];
 
+        assert_expectations(controller, &expectations);
+    }
+
+    #[test]
+    fn test_double_tap() {
+        let controller = SingleTouch::new(1, 1, 5).with_double_tap(3).adapter();
+
+        let expectations: [&[(bool, InputResult<()>)]; 4] = [
+            // single tap is reported after the double tap window expires
+            &[
+                (false, InputState::Idle.into()),
+                (true, InputState::Idle.into()),
+                (true, InputState::InProgress(63).into()),
+                (false, InputState::Idle.into()),
+                (false, InputState::Idle.into()),
+                (false, InputState::Idle.into()),
+                (false, Interaction::Navigation(Navigation::Next).into()),
+                (false, InputState::Idle.into()),
+            ],
+            // double tap selects the previous item
+            &[
+                (false, InputState::Idle.into()),
+                (true, InputState::Idle.into()),
+                (true, InputState::InProgress(63).into()),
+                (false, InputState::Idle.into()),
+                (false, InputState::Idle.into()),
+                (true, InputState::Idle.into()),
+                (true, InputState::InProgress(63).into()),
+                (false, Interaction::Navigation(Navigation::Previous).into()),
+                (false, InputState::Idle.into()),
+                (false, InputState::Idle.into()),
+                (false, InputState::Idle.into()),
+            ],
+            // bouncing second press does not cancel the first tap
+            &[
+                (false, InputState::Idle.into()),
+                (true, InputState::Idle.into()),
+                (true, InputState::InProgress(63).into()),
+                (false, InputState::Idle.into()),
+                (true, InputState::Idle.into()),
+                (false, InputState::Idle.into()),
+                (false, InputState::Idle.into()),
+                (false, Interaction::Navigation(Navigation::Next).into()),
+            ],
+            // tap followed by a long press only activates
+            &[
+                (false, InputState::Idle.into()),
+                (true, InputState::Idle.into()),
+                (true, InputState::InProgress(63).into()),
+                (false, InputState::Idle.into()),
+                (true, InputState::Idle.into()),
+                (true, InputState::InProgress(63).into()),
+                (true, InputState::InProgress(127).into()),
+                (true, InputState::InProgress(191).into()),
+                (true, Interaction::Action(Action::Select).into()),
+                (false, InputState::Idle.into()),
+                (false, InputState::Idle.into()),
+                (false, InputState::Idle.into()),
+                (false, InputState::Idle.into()),
+            ],
+        ];
+
+        assert_expectations(controller, &expectations);
+    }
+
+    #[test]
+    fn test_back() {
+        let controller = SingleTouch::new(1, 1, 5).with_back(9, 42).adapter();
+
+        let expectations: [&[(bool, InputResult<u8>)]; 3] = [
+            // short press is not affected
+            &[
+                (false, InputState::Idle.into()),
+                (true, InputState::Idle.into()),
+                (true, InputState::InProgress(63).into()),
+                (false, Interaction::Navigation(Navigation::Next).into()),
+            ],
+            // long press activates on release
+            &[
+                (false, InputState::Idle.into()),
+                (true, InputState::Idle.into()),
+                (true, InputState::InProgress(63).into()),
+                (true, InputState::InProgress(127).into()),
+                (true, InputState::InProgress(191).into()),
+                (true, InputState::InProgress(0).into()),
+                (true, InputState::InProgress(63).into()),
+                (false, Interaction::Action(Action::Select).into()),
+                (false, InputState::Idle.into()),
+            ],
+            // very long press returns, then waits for release
+            &[
+                (false, InputState::Idle.into()),
+                (true, InputState::Idle.into()),
+                (true, InputState::InProgress(63).into()),
+                (true, InputState::InProgress(127).into()),
+                (true, InputState::InProgress(191).into()),
+                (true, InputState::InProgress(0).into()),
+                (true, InputState::InProgress(63).into()),
+                (true, InputState::InProgress(127).into()),
+                (true, InputState::InProgress(191).into()),
+                (true, Interaction::Action(Action::Return(42)).into()),
+                (true, InputState::Idle.into()),
+                (true, InputState::Idle.into()),
+                (false, InputState::Idle.into()),
+                (true, InputState::Idle.into()),
+                (true, InputState::InProgress(63).into()),
+                (false, Interaction::Navigation(Navigation::Next).into()),
+            ],
+        ];
+
+        assert_expectations(controller, &expectations);
+    }
+
+    #[test]
+    fn test_help() {
+        let controller = SingleTouch::new(1, 1, 5).with_help(7).adapter();
+
+        let expectations: [&[(bool, InputResult<()>)]; 2] = [
+            // long press activates on release
+            &[
+                (false, InputState::Idle.into()),
+                (true, InputState::Idle.into()),
+                (true, InputState::InProgress(63).into()),
+                (true, InputState::InProgress(127).into()),
+                (true, InputState::InProgress(191).into()),
+                (true, InputState::InProgress(0).into()),
+                (false, Interaction::Action(Action::Select).into()),
+            ],
+            // longer press opens the help, then waits for release
+            &[
+                (false, InputState::Idle.into()),
+                (true, InputState::Idle.into()),
+                (true, InputState::InProgress(63).into()),
+                (true, InputState::InProgress(127).into()),
+                (true, InputState::InProgress(191).into()),
+                (true, InputState::InProgress(0).into()),
+                (true, InputState::InProgress(127).into()),
+                (true, Interaction::Action(Action::Help).into()),
+                (true, InputState::Idle.into()),
+                (false, InputState::Idle.into()),
+            ],
+        ];
+
+        assert_expectations(controller, &expectations);
+
+        let controller = SingleTouch::new(1, 1, 5)
+            .with_back(9, 42)
+            .with_help(7)
+            .adapter();
+
+        let expectations: [&[(bool, InputResult<u8>)]; 2] = [
+            // help opens on release
+            &[
+                (false, InputState::Idle.into()),
+                (true, InputState::Idle.into()),
+                (true, InputState::InProgress(63).into()),
+                (true, InputState::InProgress(127).into()),
+                (true, InputState::InProgress(191).into()),
+                (true, InputState::InProgress(0).into()),
+                (true, InputState::InProgress(127).into()),
+                (true, InputState::InProgress(0).into()),
+                (false, Interaction::Action(Action::Help).into()),
+            ],
+            // holding even longer still returns
+            &[
+                (false, InputState::Idle.into()),
+                (true, InputState::Idle.into()),
+                (true, InputState::InProgress(63).into()),
+                (true, InputState::InProgress(127).into()),
+                (true, InputState::InProgress(191).into()),
+                (true, InputState::InProgress(0).into()),
+                (true, InputState::InProgress(127).into()),
+                (true, InputState::InProgress(0).into()),
+                (true, InputState::InProgress(127).into()),
+                (true, Interaction::Action(Action::Return(42)).into()),
+                (false, InputState::Idle.into()),
+            ],
+        ];
+
+        assert_expectations(controller, &expectations);
+    }
+
+    #[test]
+    #[should_panic(expected = "The help time must be longer than a long press")]
+    fn help_time_must_be_longer_than_a_long_press() {
+        let _ = InputAdapterSource::<()>::adapter(&SingleTouch::new(1, 1, 5).with_help(5));
+    }
+
+    #[test]
+    #[should_panic(expected = "shorter than the back time")]
+    fn help_time_must_be_shorter_than_back_time() {
+        let _ = SingleTouch::new(1, 1, 5)
+            .with_back(7, 42)
+            .with_help(8)
+            .adapter();
+    }
+
+    #[test]
+    #[should_panic(expected = "Auto advance requires double tap detection")]
+    fn auto_advance_requires_double_tap() {
+        let _ = InputAdapterSource::<()>::adapter(&SingleTouch::new(1, 1, 5).with_auto_advance(2));
+    }
+
+    #[test]
+    fn test_auto_advance() {
+        let controller = SingleTouch::new(1, 1, 5)
+            .with_double_tap(3)
+            .with_auto_advance(2)
+            .adapter();
+
+        let expectations: [&[(bool, InputResult<()>)]; 2] = [
+            // tap, then hold advances repeatedly
+            &[
+                (false, InputState::Idle.into()),
+                (true, InputState::Idle.into()),
+                (true, InputState::InProgress(63).into()),
+                (false, InputState::Idle.into()),
+                (true, InputState::Idle.into()),
+                (true, InputState::InProgress(63).into()),
+                (true, InputState::InProgress(127).into()),
+                (true, InputState::InProgress(191).into()),
+                (true, Interaction::Navigation(Navigation::Next).into()),
+                (true, InputState::Idle.into()),
+                (true, Interaction::Navigation(Navigation::Next).into()),
+                (true, InputState::Idle.into()),
+                (false, InputState::Idle.into()),
+                (false, InputState::Idle.into()),
+                (false, InputState::Idle.into()),
+                (false, InputState::Idle.into()),
+            ],
+            // a single long press still activates
+            &[
+                (false, InputState::Idle.into()),
+                (true, InputState::Idle.into()),
+                (true, InputState::InProgress(63).into()),
+                (true, InputState::InProgress(127).into()),
+                (true, InputState::InProgress(191).into()),
+                (true, Interaction::Action(Action::Select).into()),
+                (false, InputState::Idle.into()),
+            ],
+        ];
+
+        assert_expectations(controller, &expectations);
+    }
+
+    fn assert_expectations<R>(
+        controller: impl InputAdapter<Input = bool, Value = R>,
+        expectations: &[&[(bool, InputResult<R>)]],
+    ) where
+        R: PartialEq + Debug,
+    {
         for (row, &inputs) in expectations.iter().enumerate() {
             let mut controller_state = Default::default();
 
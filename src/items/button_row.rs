@@ -0,0 +1,279 @@
+use embedded_graphics::{
+    mono_font::MonoTextStyle,
+    pixelcolor::BinaryColor,
+    prelude::{DrawTarget, Point, Size},
+    primitives::Rectangle,
+    text::{renderer::TextRenderer, Baseline, Text},
+    Drawable,
+};
+use embedded_layout::View;
+
+use crate::{
+    interaction::Direction,
+    items::{starts_with_ignore_case, Marker, MenuLine, MenuListItem},
+};
+
+/// A row of horizontally arranged buttons, e.g. `OK` and `Cancel`.
+///
+/// Left and right navigation moves between the buttons, and the selection indicator only
+/// highlights the selected button. Activating the row returns the index of the selected button,
+/// converted to the menu's return type.
+///
+/// The title of the row is not displayed, but it is used to filter the menu, to jump to the row
+/// by its first letter, and as the title of its help page. Grid and carousel layouts use left and
+/// right navigation themselves, so a row is only useful in a [`MenuLayout::List`].
+///
+/// [`MenuLayout::List`]: crate::MenuLayout::List
+pub struct ButtonRow<T, R, const N: usize>
+where
+    T: AsRef<str>,
+{
+    title: T,
+    labels: [T; N],
+    convert: fn(usize) -> R,
+    selected: usize,
+    id: Option<u16>,
+    filtered_out: bool,
+    /// The width of each label, measured by `set_style`.
+    widths: [u32; N],
+    /// Space between two buttons.
+    gap: u32,
+    line: MenuLine,
+}
+
+impl<T, R, const N: usize> ButtonRow<T, R, N>
+where
+    T: AsRef<str>,
+{
+    pub fn new(title: T, labels: [T; N], convert: fn(usize) -> R) -> Self {
+        assert!(N > 0, "A button row needs at least one button");
+
+        Self {
+            title,
+            labels,
+            convert,
+            selected: 0,
+            id: None,
+            filtered_out: false,
+            widths: [0; N],
+            gap: 0,
+            line: MenuLine::empty(),
+        }
+    }
+
+    /// Sets the initially selected button.
+    pub fn with_selected(self, index: usize) -> Self {
+        Self {
+            selected: index.min(N - 1),
+            ..self
+        }
+    }
+
+    /// Assigns an identifier to the item that is independent of its position in the menu.
+    pub fn with_id(self, id: u16) -> Self {
+        Self {
+            id: Some(id),
+            ..self
+        }
+    }
+
+    /// Returns the index of the selected button.
+    pub fn selected(&self) -> usize {
+        self.selected
+    }
+
+    /// Changes the title of the row.
+    pub fn set_title(&mut self, title: T) {
+        self.title = title;
+    }
+
+    /// Returns the horizontal offset of the `nth` button, relative to the row.
+    fn offset_of(&self, nth: usize) -> u32 {
+        self.widths[..nth]
+            .iter()
+            .map(|width| width + self.gap)
+            .sum()
+    }
+}
+
+impl<T, R, const N: usize> Marker for ButtonRow<T, R, N> where T: AsRef<str> {}
+
+impl<T, R, const N: usize> MenuListItem<R> for ButtonRow<T, R, N>
+where
+    T: AsRef<str>,
+{
+    fn value_of(&self) -> R {
+        (self.convert)(self.selected)
+    }
+
+    fn interact(&mut self) -> R {
+        self.value_of()
+    }
+
+    fn step(&mut self, direction: Direction) -> Option<R> {
+        // Moving between the buttons doesn't change a value.
+        self.selected = match direction {
+            Direction::Left => self.selected.saturating_sub(1),
+            Direction::Right => (self.selected + 1).min(N - 1),
+        };
+        None
+    }
+
+    fn selectable(&self) -> bool {
+        self.is_visible()
+    }
+
+    fn title(&self) -> &str {
+        self.title.as_ref()
+    }
+
+    fn filter(&mut self, filter: &str) {
+        self.filtered_out = !starts_with_ignore_case(self.title(), filter);
+    }
+
+    fn is_visible(&self) -> bool {
+        !self.filtered_out
+    }
+
+    fn id(&self) -> Option<u16> {
+        self.id
+    }
+
+    fn selection_area(&self) -> Option<Rectangle> {
+        Some(Rectangle::new(
+            Point::new(self.offset_of(self.selected) as i32, 0),
+            Size::new(self.widths[self.selected], self.line.bounds().size.height),
+        ))
+    }
+
+    fn set_style(&mut self, text_style: &MonoTextStyle<'_, BinaryColor>) {
+        for (width, label) in self.widths.iter_mut().zip(self.labels.iter()) {
+            *width = text_style
+                .measure_string(label.as_ref(), Point::zero(), Baseline::Top)
+                .bounding_box
+                .size
+                .width;
+        }
+        self.gap = text_style.font.character_size.width;
+        self.line = MenuLine::new("", text_style);
+    }
+
+    fn draw_styled<D>(
+        &self,
+        text_style: &MonoTextStyle<'static, BinaryColor>,
+        display: &mut D,
+    ) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = BinaryColor>,
+    {
+        if !self.is_visible() {
+            return Ok(());
+        }
+
+        let top_left = self.line.bounds().top_left;
+
+        for (nth, label) in self.labels.iter().enumerate() {
+            let position = top_left + Point::new(self.offset_of(nth) as i32, 0);
+            Text::with_baseline(label.as_ref(), position, *text_style, Baseline::Top)
+                .draw(display)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<T, R, const N: usize> View for ButtonRow<T, R, N>
+where
+    T: AsRef<str>,
+{
+    fn translate_impl(&mut self, by: Point) {
+        self.line.translate_mut(by);
+    }
+
+    fn bounds(&self) -> Rectangle {
+        let bounds = self.line.bounds();
+
+        if self.is_visible() {
+            bounds
+        } else {
+            Rectangle::new(bounds.top_left, Size::new(bounds.size.width, 0))
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use embedded_graphics::{mock_display::MockDisplay, mono_font::ascii::FONT_6X10};
+
+    use super::*;
+    use crate::{
+        interaction::{Action, Interaction, Navigation},
+        Menu,
+    };
+
+    #[test]
+    fn left_and_right_move_between_buttons() {
+        let mut row = ButtonRow::new("Actions", ["OK", "Apply", "Cancel"], |index| index);
+        row.set_style(&MonoTextStyle::new(&FONT_6X10, BinaryColor::On));
+
+        assert_eq!(row.step(Direction::Left), None);
+        assert_eq!(row.value_of(), 0);
+        assert_eq!(
+            row.selection_area(),
+            Some(Rectangle::new(Point::zero(), Size::new(12, 9)))
+        );
+
+        row.step(Direction::Right);
+        assert_eq!(row.interact(), 1);
+        assert_eq!(
+            row.selection_area(),
+            Some(Rectangle::new(Point::new(18, 0), Size::new(30, 9)))
+        );
+
+        row.step(Direction::Right);
+        row.step(Direction::Right);
+        assert_eq!(row.value_of(), 2);
+        assert_eq!(row.title(), "Actions");
+    }
+
+    #[test]
+    fn button_rows_navigate_horizontally() {
+        let mut menu = Menu::build("Title")
+            .add_item("First", (), |_| 0)
+            .add_menu_item(ButtonRow::new(
+                "Actions",
+                ["OK", "Apply", "Cancel"],
+                |index| index + 10,
+            ))
+            .build();
+
+        menu.interact(Interaction::Navigation(Navigation::Next));
+        assert_eq!(
+            menu.interact(Interaction::Navigation(Navigation::Right)),
+            None
+        );
+        menu.interact(Interaction::Navigation(Navigation::Right));
+        menu.interact(Interaction::Navigation(Navigation::Left));
+        assert_eq!(menu.selected_value(), 11);
+
+        let mut display = MockDisplay::<BinaryColor>::new();
+        display.set_allow_overdraw(true);
+        menu.update(&display);
+        menu.draw(&mut display).unwrap();
+
+        // The line indicator marks the left edge of the selected button
+        assert_eq!(display.get_pixel(Point::new(18, 24)), Some(BinaryColor::On));
+        assert_eq!(display.get_pixel(Point::new(0, 24)), None);
+
+        assert_eq!(menu.interact(Interaction::Action(Action::Select)), Some(11));
+
+        // The row is found by its own title, not by the selected button
+        menu.interact(Interaction::Navigation(Navigation::Previous));
+        menu.interact(Interaction::Navigation(Navigation::JumpToLetter('a')));
+        assert_eq!(menu.selected_value(), 11);
+        menu.set_filter("Apply");
+        assert!(!menu.selectable(1));
+        menu.set_filter("act");
+        assert!(menu.selectable(1));
+    }
+}
@@ -0,0 +1,361 @@
+use core::any::Any;
+
+use embedded_graphics::{
+    mono_font::MonoTextStyle,
+    pixelcolor::BinaryColor,
+    prelude::{DrawTarget, Point},
+    primitives::Rectangle,
+};
+use embedded_layout::View;
+
+use crate::{
+    interaction::Direction,
+    items::{Conditions, Marker, MenuListItem},
+    storage::SettingsStorage,
+};
+
+/// The question a menu asks before activating an item, and the labels of the answers.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Confirmation {
+    question: &'static str,
+    cancel_label: &'static str,
+    confirm_label: &'static str,
+    hold_ticks: Option<u32>,
+}
+
+impl Confirmation {
+    /// A confirmation with "No" and "Yes" answers.
+    pub const fn new(question: &'static str) -> Self {
+        Self {
+            question,
+            cancel_label: "No",
+            confirm_label: "Yes",
+            hold_ticks: None,
+        }
+    }
+
+    /// Replaces the labels of the answers.
+    pub const fn with_labels(self, cancel: &'static str, confirm: &'static str) -> Self {
+        Self {
+            cancel_label: cancel,
+            confirm_label: confirm,
+            ..self
+        }
+    }
+
+    /// Requires the user to hold the select input for `ticks` calls of
+    /// [`Menu::update`](crate::Menu::update) to confirm.
+    ///
+    /// The select input is held while the input adapter reports progress, or while it reports a
+    /// selection on every update, e.g. when [`Programmed`](crate::interaction::programmed::Programmed)
+    /// input is sent for as long as a button is pressed.
+    pub const fn with_hold_to_confirm(self, ticks: u32) -> Self {
+        Self {
+            hold_ticks: Some(ticks),
+            ..self
+        }
+    }
+
+    pub const fn question(&self) -> &'static str {
+        self.question
+    }
+
+    /// Returns the label of the answer that closes the dialog without activating the item.
+    pub const fn cancel_label(&self) -> &'static str {
+        self.cancel_label
+    }
+
+    /// Returns the label of the answer that activates the item.
+    pub const fn confirm_label(&self) -> &'static str {
+        self.confirm_label
+    }
+
+    /// Returns how long the select input needs to be held to confirm, if it needs to be held.
+    pub const fn hold_ticks(&self) -> Option<u32> {
+        self.hold_ticks
+    }
+}
+
+/// Wraps a menu item so that activating it asks the user for confirmation first.
+///
+/// The menu displays a Yes/No dialog when the item is activated, and only activates the item and
+/// returns its value if the user selects "Yes". The answers can be renamed with
+/// [`Confirm::with_labels`].
+pub struct Confirm<I> {
+    pub item: I,
+    pub confirmation: Confirmation,
+}
+
+impl<I> Confirm<I> {
+    pub const fn new(item: I, question: &'static str) -> Self {
+        Self {
+            item,
+            confirmation: Confirmation::new(question),
+        }
+    }
+
+    /// Replaces the "No" and "Yes" labels of the dialog.
+    pub fn with_labels(mut self, cancel: &'static str, confirm: &'static str) -> Self {
+        self.confirmation = self.confirmation.with_labels(cancel, confirm);
+        self
+    }
+
+    /// Requires the user to hold the select input for `ticks` updates to confirm. See
+    /// [`Confirmation::with_hold_to_confirm`].
+    pub fn hold_to_confirm(mut self, ticks: u32) -> Self {
+        self.confirmation = self.confirmation.with_hold_to_confirm(ticks);
+        self
+    }
+}
+
+impl<I> Marker for Confirm<I> {}
+
+impl<I, R> MenuListItem<R> for Confirm<I>
+where
+    I: MenuListItem<R>,
+{
+    fn value_of(&self) -> R {
+        self.item.value_of()
+    }
+
+    fn interact(&mut self) -> R {
+        self.item.interact()
+    }
+
+    fn interact_changed(&mut self) -> (R, bool) {
+        self.item.interact_changed()
+    }
+
+    fn set_style(&mut self, text_style: &MonoTextStyle<'_, BinaryColor>) {
+        self.item.set_style(text_style);
+    }
+
+    fn selectable(&self) -> bool {
+        self.item.selectable()
+    }
+
+    fn step(&mut self, direction: Direction) -> Option<R> {
+        self.item.step(direction)
+    }
+
+    fn title(&self) -> &str {
+        self.item.title()
+    }
+
+    fn filter(&mut self, filter: &str) {
+        self.item.filter(filter);
+    }
+
+    fn is_visible(&self) -> bool {
+        self.item.is_visible()
+    }
+
+    fn set_visible(&mut self, visible: bool) -> bool {
+        self.item.set_visible(visible)
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.item.is_enabled()
+    }
+
+    fn set_enabled(&mut self, enabled: bool) -> bool {
+        self.item.set_enabled(enabled)
+    }
+
+    fn any_value(&self) -> Option<&dyn Any> {
+        self.item.any_value()
+    }
+
+    fn set_any_value(&mut self, value: &dyn Any) -> bool {
+        self.item.set_any_value(value)
+    }
+
+    fn conditions(&self) -> Conditions {
+        self.item.conditions()
+    }
+
+    fn confirmation(&self) -> Option<Confirmation> {
+        Some(self.confirmation)
+    }
+
+    fn selection_area(&self) -> Option<Rectangle> {
+        self.item.selection_area()
+    }
+
+    fn help(&self) -> Option<&str> {
+        self.item.help()
+    }
+
+    fn id(&self) -> Option<u16> {
+        self.item.id()
+    }
+
+    fn load(&mut self, storage: &dyn SettingsStorage) {
+        self.item.load(storage);
+    }
+
+    fn store(&self, storage: &mut dyn SettingsStorage) -> bool {
+        self.item.store(storage)
+    }
+
+    fn stored_value(&self) -> Option<u32> {
+        self.item.stored_value()
+    }
+
+    fn set_stored_value(&mut self, value: u32) -> bool {
+        self.item.set_stored_value(value)
+    }
+
+    fn draw_styled<D>(
+        &self,
+        text_style: &MonoTextStyle<'static, BinaryColor>,
+        display: &mut D,
+    ) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = BinaryColor>,
+    {
+        self.item.draw_styled(text_style, display)
+    }
+}
+
+impl<I> View for Confirm<I>
+where
+    I: View,
+{
+    fn translate_impl(&mut self, by: Point) {
+        self.item.translate_impl(by);
+    }
+
+    fn bounds(&self) -> Rectangle {
+        self.item.bounds()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::vec::Vec;
+
+    use embedded_graphics::{mock_display::MockDisplay, pixelcolor::BinaryColor, Drawable};
+
+    use crate::{
+        interaction::{single_touch::SingleTouch, Action, Interaction, Navigation},
+        items::{Confirm, MenuItem},
+        Menu, MenuStyle,
+    };
+
+    #[test]
+    fn confirmation_dialog() {
+        let mut menu = Menu::build("Title")
+            .add_item("Save", (), |_| 1)
+            .add_menu_item(
+                Confirm::new(
+                    MenuItem::new("Reset", ()).with_value_converter(|_| 2),
+                    "Reset 3 settings?",
+                )
+                .with_labels("Keep", "Reset"),
+            )
+            .build();
+
+        menu.interact(Interaction::Navigation(Navigation::Next));
+
+        // "Keep" is selected by default
+        assert_eq!(menu.interact(Interaction::Action(Action::Select)), None);
+        let confirmation = menu.pending_confirmation().unwrap();
+        assert_eq!(confirmation.question(), "Reset 3 settings?");
+        assert_eq!(
+            (confirmation.cancel_label(), confirmation.confirm_label()),
+            ("Keep", "Reset")
+        );
+        assert_eq!(menu.interact(Interaction::Action(Action::Select)), None);
+        assert!(menu.pending_confirmation().is_none());
+
+        // Navigation toggles the answer without moving the selection
+        menu.interact(Interaction::Action(Action::Select));
+        menu.interact(Interaction::Navigation(Navigation::Previous));
+        menu.interact(Interaction::Navigation(Navigation::Right));
+        menu.interact(Interaction::Navigation(Navigation::Left));
+        menu.interact(Interaction::Navigation(Navigation::Beginning));
+        assert_eq!(menu.selected_value(), 2);
+
+        let mut display = MockDisplay::<BinaryColor>::new();
+        display.set_allow_overdraw(true);
+        menu.update(&display);
+        menu.draw(&mut display).unwrap();
+
+        assert_eq!(menu.interact(Interaction::Action(Action::Select)), Some(2));
+        assert!(menu.pending_confirmation().is_none());
+
+        // Returning closes the dialog
+        menu.interact(Interaction::Action(Action::Select));
+        assert_eq!(menu.interact(Interaction::Action(Action::Return(0))), None);
+        assert!(menu.pending_confirmation().is_none());
+    }
+
+    #[test]
+    fn confirmation_can_require_holding() {
+        let mut display = MockDisplay::<BinaryColor>::new();
+        display.set_allow_overdraw(true);
+
+        // Programmed input is held by selecting on every update
+        let mut menu = Menu::build("Title")
+            .add_menu_item(
+                Confirm::new(
+                    MenuItem::new("Reset", ()).with_value_converter(|_| 2),
+                    "Reset all settings?",
+                )
+                .hold_to_confirm(3),
+            )
+            .build();
+
+        menu.interact(Interaction::Action(Action::Select));
+        menu.interact(Interaction::Navigation(Navigation::Next));
+
+        // Releasing early starts over
+        for _ in 0..2 {
+            assert_eq!(menu.interact(Interaction::Action(Action::Select)), None);
+            menu.update(&display);
+        }
+        menu.update(&display);
+
+        for _ in 0..3 {
+            assert_eq!(menu.interact(Interaction::Action(Action::Select)), None);
+            menu.update(&display);
+            menu.draw(&mut display).unwrap();
+        }
+        assert_eq!(menu.interact(Interaction::Action(Action::Select)), Some(2));
+        assert!(menu.pending_confirmation().is_none());
+
+        // Single touch input is held while the button is pressed
+        let input = SingleTouch::new(0, 0, 5);
+        let mut menu = Menu::with_style("Title", MenuStyle::default().with_input_adapter(input))
+            .add_menu_item(
+                Confirm::new(
+                    MenuItem::new("Reset", ()).with_value_converter(|_| 2),
+                    "Reset all settings?",
+                )
+                .hold_to_confirm(8),
+            )
+            .build();
+
+        for pressed in [false, true, true, true, true, true, false] {
+            menu.interact(pressed);
+        }
+        assert!(menu.pending_confirmation().is_some());
+
+        // Short press toggles to "Yes"
+        menu.interact(true);
+        menu.interact(false);
+
+        let mut results = Vec::new();
+        for _ in 0..9 {
+            results.push(menu.interact(true));
+            menu.update(&display);
+        }
+        assert_eq!(
+            results,
+            [None, None, None, None, None, None, None, None, Some(2)]
+        );
+        menu.interact(false);
+        assert!(menu.pending_confirmation().is_none());
+    }
+}
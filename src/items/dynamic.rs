@@ -0,0 +1,370 @@
+//! Object-safe menu items.
+//!
+//! [`MenuListItem`] can't be used as a trait object, because drawing is generic over the display
+//! type. [`DynMenuListItem`] is implemented for every [`MenuListItem`] and draws into a type-erased
+//! [`DynDrawTarget`] instead. References to (and, with the `alloc` feature, boxes of)
+//! `dyn DynMenuListItem` implement [`MenuListItem`], so a menu's structure can be decided at
+//! runtime:
+//!
+//! ```rust
+//! use embedded_menu::{items::{DynMenuListItem, MenuItem}, Menu};
+//!
+//! let mut checkbox = MenuItem::new("Checkbox", false).with_value_converter(|v| v as u8);
+//! let mut action = MenuItem::new("Action", ()).with_value_converter(|_| 2);
+//!
+//! let mut items: [&mut dyn DynMenuListItem<u8>; 2] = [&mut checkbox, &mut action];
+//!
+//! let menu = Menu::build("Menu").add_menu_items(&mut items[..]).build();
+//! ```
+
+use core::any::Any;
+
+use embedded_graphics::{
+    draw_target::DrawTarget, geometry::Dimensions, mono_font::MonoTextStyle,
+    pixelcolor::BinaryColor, prelude::Point, primitives::Rectangle, Pixel,
+};
+use embedded_layout::prelude::*;
+
+use crate::{
+    interaction::Direction,
+    items::{Conditions, Confirmation, Marker, MenuListItem},
+    storage::SettingsStorage,
+};
+
+/// Error returned by [`DynDrawTarget`] when the underlying display failed to draw.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DrawError;
+
+/// A type-erased draw target.
+pub struct DynDrawTarget<'a> {
+    bounding_box: Rectangle,
+    draw: &'a mut dyn FnMut(&mut dyn Iterator<Item = Pixel<BinaryColor>>) -> bool,
+}
+
+impl<'a> DynDrawTarget<'a> {
+    /// Creates a draw target that passes pixels to `draw`. `draw` returns `false` if drawing failed.
+    pub fn new(
+        bounding_box: Rectangle,
+        draw: &'a mut dyn FnMut(&mut dyn Iterator<Item = Pixel<BinaryColor>>) -> bool,
+    ) -> Self {
+        Self { bounding_box, draw }
+    }
+}
+
+impl Dimensions for DynDrawTarget<'_> {
+    fn bounding_box(&self) -> Rectangle {
+        self.bounding_box
+    }
+}
+
+impl DrawTarget for DynDrawTarget<'_> {
+    type Color = BinaryColor;
+    type Error = DrawError;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        if (self.draw)(&mut pixels.into_iter()) {
+            Ok(())
+        } else {
+            Err(DrawError)
+        }
+    }
+}
+
+/// Draws a `dyn DynMenuListItem` into a concrete display.
+fn draw_dyn<R, D>(
+    item: &dyn DynMenuListItem<R>,
+    text_style: &MonoTextStyle<'static, BinaryColor>,
+    display: &mut D,
+) -> Result<(), D::Error>
+where
+    D: DrawTarget<Color = BinaryColor>,
+{
+    let bounding_box = display.bounding_box();
+    let mut error = None;
+
+    let mut draw =
+        |pixels: &mut dyn Iterator<Item = Pixel<BinaryColor>>| match display.draw_iter(pixels) {
+            Ok(()) => true,
+            Err(e) => {
+                error = Some(e);
+                false
+            }
+        };
+
+    // The only error the item can see is the one we have stored.
+    _ = item.draw_dyn(text_style, &mut DynDrawTarget::new(bounding_box, &mut draw));
+
+    match error {
+        Some(e) => Err(e),
+        None => Ok(()),
+    }
+}
+
+/// Calls `$callback!` with `$args` and the list of [`MenuListItem`] methods that are forwarded
+/// as they are, grouped by their receiver.
+///
+/// Drawing is generic over the display type, so it is forwarded separately.
+macro_rules! with_forwarded_methods {
+    ($callback:ident!($($args:tt)*)) => {
+        $callback! {
+            ($($args)*)
+            shared {
+                /// Returns the value of the selected item, without interacting with it.
+                fn value_of() -> R;
+
+                /// Returns whether the list item is selectable.
+                fn selectable() -> bool;
+
+                /// Returns the title of the list item.
+                fn title() -> &str;
+
+                /// Returns whether the list item is displayed.
+                fn is_visible() -> bool;
+
+                /// Returns whether the list item is enabled.
+                fn is_enabled() -> bool;
+
+                /// Returns the value of the list item, for [`Condition`]s that compare it by its
+                /// type.
+                ///
+                /// [`Condition`]: crate::items::Condition
+                fn any_value() -> Option<&dyn Any>;
+
+                /// Returns the conditions that control whether the list item is visible or enabled.
+                fn conditions() -> Conditions;
+
+                /// Returns the question the menu asks before activating the list item, if any.
+                fn confirmation() -> Option<Confirmation>;
+
+                /// Returns the part of the list item that the selection indicator highlights,
+                /// relative to the top left corner of the list item.
+                fn selection_area() -> Option<Rectangle>;
+
+                /// Returns the help text of the list item, if any.
+                fn help() -> Option<&str>;
+
+                /// Returns the identifier of the list item, if it has one.
+                fn id() -> Option<u16>;
+
+                /// Writes the value of the list item into `storage`, if the list item is bound to
+                /// a key.
+                ///
+                /// Returns `false` if `storage` failed to store the value.
+                fn store(storage: &mut dyn SettingsStorage) -> bool;
+
+                /// Returns the value of the list item in its [`SettingsStorage`] representation, if
+                /// it has one.
+                fn stored_value() -> Option<u32>;
+            }
+            exclusive {
+                fn interact() -> R;
+
+                /// Interacts with the list item, and returns whether its value changed.
+                fn interact_changed() -> (R, bool);
+
+                fn set_style(text_style: &MonoTextStyle<'_, BinaryColor>);
+
+                /// Handles left and right navigation while the list item is selected.
+                fn step(direction: Direction) -> Option<R>;
+
+                /// Hides the list item if it doesn't match `filter`.
+                fn filter(filter: &str);
+
+                /// Shows or hides the list item. Returns whether its visibility changed.
+                fn set_visible(visible: bool) -> bool;
+
+                /// Enables or disables the list item. Returns whether it changed.
+                fn set_enabled(enabled: bool) -> bool;
+
+                /// Changes the value of the list item to `value`, if it has the same type.
+                fn set_any_value(value: &dyn Any) -> bool;
+
+                /// Loads the value of the list item from `storage`, if the list item is bound to a
+                /// key.
+                fn load(storage: &dyn SettingsStorage);
+
+                /// Changes the value of the list item to the one represented by `value`.
+                fn set_stored_value(value: u32) -> bool;
+            }
+        }
+    };
+}
+
+macro_rules! declare_dyn_menu_list_item {
+    (
+        ()
+        shared { $($(#[$shared_attr:meta])* fn $shared:ident($($shared_arg:ident: $shared_ty:ty),*) -> $shared_ret:ty;)* }
+        exclusive { $($(#[$exclusive_attr:meta])* fn $exclusive:ident($($exclusive_arg:ident: $exclusive_ty:ty),*) $(-> $exclusive_ret:ty)?;)* }
+    ) => {
+        /// Object-safe version of [`MenuListItem`].
+        ///
+        /// This trait is implemented for every [`MenuListItem`], and is not meant to be implemented
+        /// directly.
+        pub trait DynMenuListItem<R>: View {
+            $(
+                $(#[$shared_attr])*
+                fn $shared(&self $(, $shared_arg: $shared_ty)*) -> $shared_ret;
+            )*
+
+            $(
+                $(#[$exclusive_attr])*
+                fn $exclusive(&mut self $(, $exclusive_arg: $exclusive_ty)*) $(-> $exclusive_ret)?;
+            )*
+
+            fn draw_dyn(
+                &self,
+                text_style: &MonoTextStyle<'static, BinaryColor>,
+                display: &mut DynDrawTarget<'_>,
+            ) -> Result<(), DrawError>;
+        }
+
+        impl<I, R> DynMenuListItem<R> for I
+        where
+            I: MenuListItem<R>,
+        {
+            $(
+                fn $shared(&self $(, $shared_arg: $shared_ty)*) -> $shared_ret {
+                    MenuListItem::$shared(self $(, $shared_arg)*)
+                }
+            )*
+
+            $(
+                fn $exclusive(&mut self $(, $exclusive_arg: $exclusive_ty)*) $(-> $exclusive_ret)? {
+                    MenuListItem::$exclusive(self $(, $exclusive_arg)*)
+                }
+            )*
+
+            fn draw_dyn(
+                &self,
+                text_style: &MonoTextStyle<'static, BinaryColor>,
+                display: &mut DynDrawTarget<'_>,
+            ) -> Result<(), DrawError> {
+                MenuListItem::draw_styled(self, text_style, display)
+            }
+        }
+    };
+}
+
+with_forwarded_methods!(declare_dyn_menu_list_item!());
+
+macro_rules! impl_menu_list_item {
+    (
+        ($ptr:ty)
+        shared { $($(#[$shared_attr:meta])* fn $shared:ident($($shared_arg:ident: $shared_ty:ty),*) -> $shared_ret:ty;)* }
+        exclusive { $($(#[$exclusive_attr:meta])* fn $exclusive:ident($($exclusive_arg:ident: $exclusive_ty:ty),*) $(-> $exclusive_ret:ty)?;)* }
+    ) => {
+        impl<'a, R> Marker for $ptr {}
+
+        impl<'a, R> View for $ptr {
+            fn translate_impl(&mut self, by: Point) {
+                (**self).translate_impl(by);
+            }
+
+            fn bounds(&self) -> Rectangle {
+                (**self).bounds()
+            }
+        }
+
+        impl<'a, R> MenuListItem<R> for $ptr {
+            $(
+                fn $shared(&self $(, $shared_arg: $shared_ty)*) -> $shared_ret {
+                    DynMenuListItem::$shared(&**self $(, $shared_arg)*)
+                }
+            )*
+
+            $(
+                fn $exclusive(&mut self $(, $exclusive_arg: $exclusive_ty)*) $(-> $exclusive_ret)? {
+                    DynMenuListItem::$exclusive(&mut **self $(, $exclusive_arg)*)
+                }
+            )*
+
+            fn draw_styled<D>(
+                &self,
+                text_style: &MonoTextStyle<'static, BinaryColor>,
+                display: &mut D,
+            ) -> Result<(), D::Error>
+            where
+                D: DrawTarget<Color = BinaryColor>,
+            {
+                draw_dyn(&**self, text_style, display)
+            }
+        }
+    };
+}
+
+with_forwarded_methods!(impl_menu_list_item!(&'a mut (dyn DynMenuListItem<R> + 'a)));
+
+#[cfg(feature = "alloc")]
+with_forwarded_methods!(impl_menu_list_item!(
+    alloc::boxed::Box<dyn DynMenuListItem<R> + 'a>
+));
+
+#[cfg(test)]
+mod test {
+    use embedded_graphics::{mock_display::MockDisplay, pixelcolor::BinaryColor, Drawable};
+
+    use crate::{
+        interaction::{Action, Interaction, Navigation},
+        items::{DynMenuListItem, MenuItem},
+        Menu,
+    };
+
+    #[test]
+    fn dyn_items_behave_like_static_items() {
+        let mut checkbox = MenuItem::new("Checkbox", false).with_value_converter(|v| v as u8 + 1);
+        let mut action = MenuItem::new("Action", ()).with_value_converter(|_| 3);
+        let mut items: [&mut dyn DynMenuListItem<u8>; 2] = [&mut checkbox, &mut action];
+
+        let mut dyn_menu = Menu::build("Menu").add_menu_items(&mut items[..]).build();
+        let mut static_menu = Menu::build("Menu")
+            .add_item("Checkbox", false, |v| v as u8 + 1)
+            .add_item("Action", (), |_| 3)
+            .build();
+
+        assert_eq!(
+            dyn_menu.interact(Interaction::Action(Action::Select)),
+            Some(2)
+        );
+        static_menu.interact(Interaction::Action(Action::Select));
+        dyn_menu.interact(Interaction::Navigation(Navigation::Next));
+        static_menu.interact(Interaction::Navigation(Navigation::Next));
+        assert_eq!(dyn_menu.selected_value(), 3);
+
+        let mut dyn_display = MockDisplay::<BinaryColor>::new();
+        let mut static_display = MockDisplay::<BinaryColor>::new();
+        dyn_display.set_allow_overdraw(true);
+        static_display.set_allow_overdraw(true);
+
+        dyn_menu.update(&dyn_display);
+        static_menu.update(&static_display);
+        dyn_menu.draw(&mut dyn_display).unwrap();
+        static_menu.draw(&mut static_display).unwrap();
+
+        dyn_display.assert_eq(&static_display);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn boxed_items() {
+        use alloc::{boxed::Box, vec::Vec};
+
+        let items: Vec<Box<dyn DynMenuListItem<u8>>> = alloc::vec![
+            Box::new(MenuItem::new("First", ()).with_value_converter(|_| 0)),
+            Box::new(
+                MenuItem::new("Section", ())
+                    .with_value_converter(|_| unreachable!())
+                    .selectable::<false>(),
+            ),
+            Box::new(MenuItem::new("Checkbox", false).with_value_converter(|v| v as u8 + 1)),
+        ];
+
+        let mut menu = Menu::build("Menu").add_menu_items(items).build();
+
+        menu.interact(Interaction::Navigation(Navigation::Next));
+        assert_eq!(menu.selected_value(), 1);
+        assert_eq!(menu.interact(Interaction::Action(Action::Select)), Some(2));
+    }
+}
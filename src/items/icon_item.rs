@@ -0,0 +1,154 @@
+use embedded_graphics::{
+    image::{Image, ImageDrawable},
+    mono_font::MonoTextStyle,
+    pixelcolor::BinaryColor,
+    prelude::{DrawTarget, Point, Size},
+    primitives::Rectangle,
+    text::{renderer::TextRenderer, Baseline, Text},
+    Drawable,
+};
+use embedded_layout::View;
+
+use crate::items::{Marker, MenuListItem};
+
+/// An icon with a caption below it, meant for menus with a
+/// [`MenuLayout::Grid`](crate::MenuLayout::Grid) layout.
+///
+/// Like everything else in the menu, the icon is drawn in the text color of the theme, so it needs
+/// to be a [`BinaryColor`] image, e.g. an `ImageRaw<BinaryColor>`. Activating the item returns its
+/// value.
+pub struct IconItem<T, I, R>
+where
+    T: AsRef<str>,
+    I: ImageDrawable<Color = BinaryColor>,
+{
+    caption: T,
+    icon: I,
+    value: R,
+    id: Option<u16>,
+    /// Measured by `set_style`.
+    caption_size: Size,
+    position: Point,
+}
+
+impl<T, I, R> IconItem<T, I, R>
+where
+    T: AsRef<str>,
+    I: ImageDrawable<Color = BinaryColor>,
+{
+    pub fn new(caption: T, icon: I, value: R) -> Self {
+        Self {
+            caption,
+            icon,
+            value,
+            id: None,
+            caption_size: Size::zero(),
+            position: Point::zero(),
+        }
+    }
+
+    /// Assigns an identifier to the item that is independent of its position in the menu.
+    pub fn with_id(self, id: u16) -> Self {
+        Self {
+            id: Some(id),
+            ..self
+        }
+    }
+
+    fn size(&self) -> Size {
+        let icon = self.icon.size();
+
+        Size::new(
+            icon.width.max(self.caption_size.width),
+            icon.height + self.caption_size.height,
+        )
+    }
+}
+
+impl<T, I, R> Marker for IconItem<T, I, R>
+where
+    T: AsRef<str>,
+    I: ImageDrawable<Color = BinaryColor>,
+{
+}
+
+impl<T, I, R> MenuListItem<R> for IconItem<T, I, R>
+where
+    T: AsRef<str>,
+    I: ImageDrawable<Color = BinaryColor>,
+    R: Clone,
+{
+    fn value_of(&self) -> R {
+        self.value.clone()
+    }
+
+    fn interact(&mut self) -> R {
+        self.value_of()
+    }
+
+    fn title(&self) -> &str {
+        self.caption.as_ref()
+    }
+
+    fn id(&self) -> Option<u16> {
+        self.id
+    }
+
+    fn selection_area(&self) -> Option<Rectangle> {
+        Some(Rectangle::new(Point::zero(), self.size()))
+    }
+
+    fn set_style(&mut self, text_style: &MonoTextStyle<'_, BinaryColor>) {
+        self.caption_size = if self.caption.as_ref().is_empty() {
+            Size::zero()
+        } else {
+            // Leave a pixel between the icon and the caption
+            text_style
+                .measure_string(self.caption.as_ref(), Point::zero(), Baseline::Top)
+                .bounding_box
+                .size
+                + Size::new(0, 1)
+        };
+    }
+
+    fn draw_styled<D>(
+        &self,
+        text_style: &MonoTextStyle<'static, BinaryColor>,
+        display: &mut D,
+    ) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = BinaryColor>,
+    {
+        let size = self.size();
+        let icon = self.icon.size();
+        let center = |width: u32| self.position + Point::new(((size.width - width) / 2) as i32, 0);
+
+        Image::new(&self.icon, center(icon.width)).draw(display)?;
+
+        if !self.caption.as_ref().is_empty() {
+            Text::with_baseline(
+                self.caption.as_ref(),
+                center(self.caption_size.width) + Point::new(0, icon.height as i32 + 1),
+                *text_style,
+                Baseline::Top,
+            )
+            .draw(display)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<T, I, R> View for IconItem<T, I, R>
+where
+    T: AsRef<str>,
+    I: ImageDrawable<Color = BinaryColor>,
+{
+    fn translate_impl(&mut self, by: Point) {
+        self.position += by;
+    }
+
+    fn bounds(&self) -> Rectangle {
+        Rectangle::new(self.position, self.size())
+    }
+}
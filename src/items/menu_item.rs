@@ -1,19 +1,54 @@
+use core::any::Any;
+
 use embedded_graphics::{
     mono_font::MonoTextStyle,
     pixelcolor::BinaryColor,
-    prelude::{DrawTarget, Point},
+    prelude::{DrawTarget, Point, Size},
     primitives::Rectangle,
 };
 use embedded_layout::View;
 
-use crate::items::{Marker, MenuLine, MenuListItem};
+use crate::{
+    interaction::Direction,
+    items::{
+        starts_with_ignore_case, Condition, Conditions, Dimmed, Marker, MenuLine, MenuListItem,
+    },
+    storage::SettingsStorage,
+};
 
 pub trait SelectValue: Sized + Clone + PartialEq {
     /// Transforms the value on interaction
     fn next(&mut self) {}
 
+    /// Transforms the value in the opposite direction of `next`
+    ///
+    /// The default implementation does nothing. Types whose `next` cycles through their values
+    /// should override it.
+    fn previous(&mut self) {}
+
     /// Returns a displayable marker for the value
     fn marker(&self) -> &str;
+
+    /// Returns the number that represents the value in a [`SettingsStorage`], or `None` if the
+    /// value can't be stored.
+    ///
+    /// The number must identify the value across firmware versions, so it should not depend on
+    /// e.g. the marker of the value. Items with values that can't be stored are not persisted. The
+    /// default implementation returns `None`.
+    ///
+    /// The derive macro stores the variants of an enum as their position in the enum, so new
+    /// variants should be added after the existing ones.
+    fn stored_value(&self) -> Option<u32> {
+        None
+    }
+
+    /// Returns the value represented by `stored`, the inverse of
+    /// [`stored_value`](Self::stored_value).
+    ///
+    /// The default implementation returns `None`.
+    fn from_stored_value(_stored: u32) -> Option<Self> {
+        None
+    }
 }
 
 impl SelectValue for bool {
@@ -21,6 +56,22 @@ impl SelectValue for bool {
         *self = !*self;
     }
 
+    fn previous(&mut self) {
+        self.next();
+    }
+
+    fn stored_value(&self) -> Option<u32> {
+        Some(*self as u32)
+    }
+
+    fn from_stored_value(stored: u32) -> Option<Self> {
+        match stored {
+            0 => Some(false),
+            1 => Some(true),
+            _ => None,
+        }
+    }
+
     fn marker(&self) -> &str {
         match *self {
             // true => "O",
@@ -41,6 +92,14 @@ impl SelectValue for () {
     fn marker(&self) -> &str {
         ""
     }
+
+    fn stored_value(&self) -> Option<u32> {
+        Some(0)
+    }
+
+    fn from_stored_value(stored: u32) -> Option<Self> {
+        (stored == 0).then_some(())
+    }
 }
 
 pub struct MenuItem<T, R, S, const SELECTABLE: bool>
@@ -51,6 +110,13 @@ where
     title_text: T,
     convert: fn(S) -> R,
     value: S,
+    storage_key: Option<&'static str>,
+    id: Option<u16>,
+    help: Option<&'static str>,
+    filtered_out: bool,
+    hidden: bool,
+    disabled: bool,
+    conditions: Conditions,
     line: MenuLine,
 }
 
@@ -64,6 +130,13 @@ where
             title_text,
             value,
             convert: |_| (),
+            storage_key: None,
+            id: None,
+            help: None,
+            filtered_out: false,
+            hidden: false,
+            disabled: false,
+            conditions: Conditions::none(),
             line: MenuLine::empty(),
         }
     }
@@ -74,24 +147,125 @@ where
     T: AsRef<str>,
     S: SelectValue,
 {
+    /// Sets the function that converts the item's value to the menu's return type.
     pub fn with_value_converter<R2>(self, convert: fn(S) -> R2) -> MenuItem<T, R2, S, SELECTABLE> {
         MenuItem {
             convert,
+            conditions: self.conditions,
             title_text: self.title_text,
             value: self.value,
+            storage_key: self.storage_key,
+            id: self.id,
+            help: self.help,
+            filtered_out: self.filtered_out,
+            hidden: self.hidden,
+            disabled: self.disabled,
             line: self.line,
         }
     }
 
+    /// Assigns an identifier to the item that is independent of its position in the menu.
+    pub fn with_id(self, id: u16) -> Self {
+        Self {
+            id: Some(id),
+            ..self
+        }
+    }
+
+    /// Sets the help text of the item. See [`MenuListItem::help`].
+    pub fn with_help(self, help: &'static str) -> Self {
+        Self {
+            help: Some(help),
+            ..self
+        }
+    }
+
+    /// Sets whether the item is initially enabled. Disabled items are grayed out and can't be
+    /// selected.
+    pub fn with_enabled(self, enabled: bool) -> Self {
+        Self {
+            disabled: !enabled,
+            ..self
+        }
+    }
+
+    /// Sets whether the item is initially visible. Hidden items take up no space.
+    pub fn with_visible(self, visible: bool) -> Self {
+        Self {
+            hidden: !visible,
+            ..self
+        }
+    }
+
+    /// Only displays the item when `condition` returns `true`.
+    pub fn visible_if(self, condition: Condition) -> Self {
+        Self {
+            conditions: Conditions {
+                visible: Some(condition),
+                ..self.conditions
+            },
+            ..self
+        }
+    }
+
+    /// Only enables the item when `condition` returns `true`.
+    pub fn enabled_if(self, condition: Condition) -> Self {
+        Self {
+            conditions: Conditions {
+                enabled: Some(condition),
+                ..self.conditions
+            },
+            ..self
+        }
+    }
+
+    /// Binds the item's value to a key of a [`SettingsStorage`].
+    ///
+    /// The value must have a [stored representation](SelectValue::stored_value).
+    pub fn with_storage_key(self, key: &'static str) -> Self {
+        debug_assert!(
+            self.value.stored_value().is_some(),
+            "Only values with a stored representation can be bound to a storage key"
+        );
+        Self {
+            storage_key: Some(key),
+            ..self
+        }
+    }
+
     /// Make the item selectable or not
     pub fn selectable<const SELECTABLE2: bool>(self) -> MenuItem<T, R, S, SELECTABLE2> {
         MenuItem {
             convert: self.convert,
+            conditions: self.conditions,
             title_text: self.title_text,
             value: self.value,
+            storage_key: self.storage_key,
+            id: self.id,
+            help: self.help,
+            filtered_out: self.filtered_out,
+            hidden: self.hidden,
+            disabled: self.disabled,
             line: self.line,
         }
     }
+
+    /// Changes the value of the item.
+    ///
+    /// Inside [`Menu::modify_items`](crate::Menu::modify_items), the menu measures the item again
+    /// afterwards.
+    pub fn set_value(&mut self, value: S) {
+        self.value = value;
+    }
+
+    /// Changes the title of the item. The item keeps `title`, which can be an owned string type to
+    /// display text that is generated at runtime.
+    ///
+    /// Inside [`Menu::modify_items`](crate::Menu::modify_items), the menu measures the item again
+    /// afterwards.
+    pub fn set_title(&mut self, title: T) {
+        self.title_text = title;
+    }
 }
 
 impl<T, R, S, const SELECTABLE: bool> Marker for MenuItem<T, R, S, SELECTABLE>
@@ -104,7 +278,7 @@ where
 impl<T, R, S, const SELECTABLE: bool> MenuListItem<R> for MenuItem<T, R, S, SELECTABLE>
 where
     T: AsRef<str>,
-    S: SelectValue,
+    S: SelectValue + 'static,
 {
     fn value_of(&self) -> R {
         (self.convert)(self.value.clone())
@@ -115,8 +289,110 @@ where
         self.value_of()
     }
 
+    fn interact_changed(&mut self) -> (R, bool) {
+        let old = self.value.clone();
+        let value = self.interact();
+        (value, self.value != old)
+    }
+
     fn selectable(&self) -> bool {
-        SELECTABLE
+        SELECTABLE && self.is_visible() && self.is_enabled()
+    }
+
+    fn title(&self) -> &str {
+        self.title_text.as_ref()
+    }
+
+    fn filter(&mut self, filter: &str) {
+        self.filtered_out = !starts_with_ignore_case(self.title(), filter);
+    }
+
+    fn is_visible(&self) -> bool {
+        !self.filtered_out && !self.hidden
+    }
+
+    fn set_visible(&mut self, visible: bool) -> bool {
+        let changed = self.hidden == visible;
+        self.hidden = !visible;
+        changed
+    }
+
+    fn is_enabled(&self) -> bool {
+        !self.disabled
+    }
+
+    fn set_enabled(&mut self, enabled: bool) -> bool {
+        let changed = self.disabled == enabled;
+        self.disabled = !enabled;
+        changed
+    }
+
+    fn any_value(&self) -> Option<&dyn Any> {
+        Some(&self.value)
+    }
+
+    fn set_any_value(&mut self, value: &dyn Any) -> bool {
+        match value.downcast_ref::<S>() {
+            Some(value) => {
+                self.value = value.clone();
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn conditions(&self) -> Conditions {
+        self.conditions
+    }
+
+    fn step(&mut self, direction: Direction) -> Option<R> {
+        let old = self.value.clone();
+
+        match direction {
+            Direction::Left => self.value.previous(),
+            Direction::Right => self.value.next(),
+        }
+
+        if self.value == old {
+            None
+        } else {
+            Some(self.value_of())
+        }
+    }
+
+    fn id(&self) -> Option<u16> {
+        self.id
+    }
+
+    fn help(&self) -> Option<&str> {
+        self.help
+    }
+
+    fn load(&mut self, storage: &dyn SettingsStorage) {
+        if let Some(stored) = self.storage_key.and_then(|key| storage.get(key)) {
+            self.set_stored_value(stored);
+        }
+    }
+
+    fn store(&self, storage: &mut dyn SettingsStorage) -> bool {
+        match (self.storage_key, self.value.stored_value()) {
+            (Some(key), Some(value)) => storage.set(key, value),
+            _ => true,
+        }
+    }
+
+    fn stored_value(&self) -> Option<u32> {
+        self.value.stored_value()
+    }
+
+    fn set_stored_value(&mut self, value: u32) -> bool {
+        match S::from_stored_value(value) {
+            Some(value) => {
+                self.value = value;
+                true
+            }
+            None => false,
+        }
     }
 
     fn set_style(&mut self, text_style: &MonoTextStyle<'_, BinaryColor>) {
@@ -148,26 +424,37 @@ where
     where
         D: DrawTarget<Color = BinaryColor>,
     {
-        self.line.draw_styled(
-            self.title_text.as_ref(),
-            self.value.marker(),
-            text_style,
-            display,
-        )
+        if !self.is_visible() {
+            return Ok(());
+        }
+
+        let (title, marker) = (self.title(), self.value.marker());
+        if self.is_enabled() {
+            self.line.draw_styled(title, marker, text_style, display)
+        } else {
+            self.line
+                .draw_styled(title, marker, text_style, &mut Dimmed(display))
+        }
     }
 }
 
 impl<T, R, S, const SELECTABLE: bool> View for MenuItem<T, R, S, SELECTABLE>
 where
     T: AsRef<str>,
-    S: SelectValue,
+    S: SelectValue + 'static,
 {
     fn translate_impl(&mut self, by: Point) {
         self.line.translate_mut(by);
     }
 
     fn bounds(&self) -> Rectangle {
-        self.line.bounds()
+        let bounds = self.line.bounds();
+
+        if self.is_visible() {
+            bounds
+        } else {
+            Rectangle::new(bounds.top_left, Size::new(bounds.size.width, 0))
+        }
     }
 }
 
@@ -188,4 +475,135 @@ mod test {
         assert_eq!(item.interact(), 0);
         assert_eq!(item.value_of(), 0);
     }
+
+    #[test]
+    fn stepping_changes_value_in_both_directions() {
+        use super::*;
+        use crate::items::MenuListItem;
+
+        #[derive(Clone, Copy, PartialEq, Debug)]
+        enum Value {
+            A,
+            B,
+            C,
+        }
+
+        impl SelectValue for Value {
+            fn next(&mut self) {
+                *self = match self {
+                    Self::A => Self::B,
+                    Self::B => Self::C,
+                    Self::C => Self::A,
+                }
+            }
+
+            fn previous(&mut self) {
+                *self = match self {
+                    Self::A => Self::C,
+                    Self::B => Self::A,
+                    Self::C => Self::B,
+                }
+            }
+
+            fn marker(&self) -> &str {
+                ""
+            }
+        }
+
+        // `next` doesn't cycle, and `previous` is not implemented
+        #[derive(Clone, Copy, PartialEq, Debug)]
+        struct Counter(u8);
+
+        impl SelectValue for Counter {
+            fn next(&mut self) {
+                self.0 = self.0.saturating_add(1);
+            }
+
+            fn marker(&self) -> &str {
+                ""
+            }
+        }
+
+        let mut item = MenuItem::new("title", Value::A).with_value_converter(|v| v);
+
+        assert_eq!(item.step(Direction::Left), Some(Value::C));
+        assert_eq!(item.step(Direction::Left), Some(Value::B));
+        assert_eq!(item.step(Direction::Right), Some(Value::C));
+        assert_eq!(item.step(Direction::Right), Some(Value::A));
+
+        let mut item = MenuItem::new("title", Counter(0)).with_value_converter(|v| v.0);
+        assert_eq!(item.step(Direction::Left), None);
+        assert_eq!(item.step(Direction::Right), Some(1));
+
+        let mut item = MenuItem::new("title", ">").with_value_converter(|_| ());
+        assert_eq!(item.step(Direction::Left), None);
+        assert_eq!(item.step(Direction::Right), None);
+    }
+
+    #[test]
+    fn disabled_items_are_dimmed() {
+        use super::*;
+        use crate::items::MenuListItem;
+        use embedded_graphics::{
+            geometry::Dimensions,
+            mock_display::MockDisplay,
+            mono_font::{ascii::FONT_6X10, MonoTextStyle},
+            primitives::PointsIter,
+        };
+
+        let style = MonoTextStyle::new(&FONT_6X10, BinaryColor::On);
+        let mut item = MenuItem::new("title", false).with_value_converter(|_| ());
+        item.set_style(&style);
+
+        let mut enabled = MockDisplay::new();
+        item.draw_styled(&style, &mut enabled).unwrap();
+
+        item.set_enabled(false);
+        let mut disabled = MockDisplay::new();
+        item.draw_styled(&style, &mut disabled).unwrap();
+
+        let lit = |display: &MockDisplay<BinaryColor>| {
+            display
+                .bounding_box()
+                .points()
+                .filter(|&p| display.get_pixel(p) == Some(BinaryColor::On))
+                .count()
+        };
+        assert!(lit(&disabled) > 0);
+        assert!(lit(&disabled) < lit(&enabled));
+    }
+
+    #[test]
+    fn stored_values_are_restored_by_their_representation() {
+        use super::*;
+        use crate::items::MenuListItem;
+
+        #[derive(Clone, Copy, PartialEq, Debug)]
+        struct Counter(u8);
+
+        impl SelectValue for Counter {
+            fn marker(&self) -> &str {
+                ""
+            }
+
+            fn stored_value(&self) -> Option<u32> {
+                Some(self.0 as u32)
+            }
+
+            fn from_stored_value(stored: u32) -> Option<Self> {
+                u8::try_from(stored).ok().map(Counter)
+            }
+        }
+
+        let mut item = MenuItem::new("Counter", Counter(0));
+        assert!(item.set_stored_value(200));
+        assert_eq!(MenuListItem::stored_value(&item), Some(200));
+        assert!(!item.set_stored_value(1000));
+        assert_eq!(MenuListItem::stored_value(&item), Some(200));
+
+        // Values without a stored representation can't be stored or restored
+        let mut item = MenuItem::new("Text", "text");
+        assert_eq!(MenuListItem::stored_value(&item), None);
+        assert!(!item.set_stored_value(0));
+    }
 }
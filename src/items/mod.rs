@@ -1,19 +1,181 @@
+pub mod button_row;
+pub mod confirm;
+pub mod dynamic;
+pub mod icon_item;
 pub mod menu_item;
 
+pub use button_row::ButtonRow;
+pub use confirm::{Confirm, Confirmation};
+pub use dynamic::DynMenuListItem;
+pub use icon_item::IconItem;
 pub use menu_item::MenuItem;
 
+use core::{any::Any, marker::PhantomData};
+
 use embedded_graphics::{
     draw_target::DrawTarget,
+    geometry::Dimensions,
     mono_font::MonoTextStyle,
     pixelcolor::BinaryColor,
     prelude::{Point, Size},
     primitives::Rectangle,
     text::{renderer::TextRenderer, Baseline},
-    Drawable,
+    Drawable, Pixel,
 };
 use embedded_layout::prelude::*;
 use embedded_text::{alignment::HorizontalAlignment, style::TextBoxStyleBuilder, TextBox};
 
+use crate::{
+    collection::MenuItemCollection, interaction::Direction, items::menu_item::SelectValue,
+    storage::SettingsStorage,
+};
+
+/// Read-only access to the values of a menu's items, passed to [`Condition`]s.
+///
+/// Values are accessed by the type of the item's value, e.g. `bool` for a checkbox, so conditions
+/// don't depend on the menu's return type.
+pub struct MenuValues<'a> {
+    items: &'a dyn ValueSource,
+}
+
+/// Object-safe subset of [`MenuItemCollection`].
+trait ValueSource {
+    fn count(&self) -> usize;
+    fn value(&self, nth: usize) -> Option<&dyn Any>;
+    fn stored_value(&self, nth: usize) -> Option<u32>;
+    fn index(&self, id: u16) -> Option<usize>;
+}
+
+/// Erases the return type of a [`MenuItemCollection`].
+struct Values<'a, C, R> {
+    items: &'a C,
+    _marker: PhantomData<R>,
+}
+
+impl<C, R> ValueSource for Values<'_, C, R>
+where
+    C: MenuItemCollection<R>,
+{
+    fn count(&self) -> usize {
+        self.items.count()
+    }
+
+    fn value(&self, nth: usize) -> Option<&dyn Any> {
+        self.items.any_value_of(nth)
+    }
+
+    fn stored_value(&self, nth: usize) -> Option<u32> {
+        self.items.stored_value_of(nth)
+    }
+
+    fn index(&self, id: u16) -> Option<usize> {
+        self.items.index_of(id)
+    }
+}
+
+impl MenuValues<'_> {
+    /// Returns the number of items in the menu.
+    pub fn count(&self) -> usize {
+        self.items.count()
+    }
+
+    /// Returns the value of the `nth` item, if it is a `V`.
+    pub fn value_of<V: Clone + 'static>(&self, nth: usize) -> Option<V> {
+        self.items.value(nth)?.downcast_ref::<V>().cloned()
+    }
+
+    /// Returns the value of the item with the given identifier, if it is a `V`.
+    pub fn value_by_id<V: Clone + 'static>(&self, id: u16) -> Option<V> {
+        self.items.index(id).and_then(|nth| self.value_of(nth))
+    }
+
+    /// Returns the value of the `nth` item in its [`SettingsStorage`] representation.
+    pub fn stored_value_of(&self, nth: usize) -> Option<u32> {
+        self.items.stored_value(nth)
+    }
+
+    /// Returns the value of the item with the given identifier in its [`SettingsStorage`]
+    /// representation.
+    pub fn stored_value_by_id(&self, id: u16) -> Option<u32> {
+        self.items
+            .index(id)
+            .and_then(|nth| self.items.stored_value(nth))
+    }
+
+    /// Returns whether the `nth` item has the given value.
+    ///
+    /// Items that don't provide their [value](MenuListItem::any_value), e.g. [`VirtualItems`],
+    /// are compared by their stored value, so their values only match if they can be stored.
+    ///
+    /// [`VirtualItems`]: crate::collection::VirtualItems
+    pub fn has_value<V: SelectValue + 'static>(&self, nth: usize, value: V) -> bool {
+        match self.items.value(nth) {
+            Some(current) => current.downcast_ref::<V>() == Some(&value),
+            None => {
+                value.stored_value().is_some() && self.stored_value_of(nth) == value.stored_value()
+            }
+        }
+    }
+
+    /// Returns whether the item with the given identifier has the given value. See
+    /// [`MenuValues::has_value`].
+    pub fn has_value_by_id<V: SelectValue + 'static>(&self, id: u16, value: V) -> bool {
+        self.items
+            .index(id)
+            .is_some_and(|nth| self.has_value(nth, value))
+    }
+}
+
+/// A predicate over the values of a menu's items.
+pub type Condition = fn(&MenuValues<'_>) -> bool;
+
+/// Conditions that decide whether an item is visible or enabled.
+///
+/// Conditions are evaluated when the menu is built and every time the user changes a value.
+#[derive(Clone, Copy, Default)]
+pub struct Conditions {
+    /// The item is only visible if this returns `true`.
+    pub visible: Option<Condition>,
+    /// The item is only enabled if this returns `true`.
+    pub enabled: Option<Condition>,
+}
+
+impl Conditions {
+    pub const fn none() -> Self {
+        Self {
+            visible: None,
+            enabled: None,
+        }
+    }
+}
+
+/// Evaluates the conditions of every item. Returns whether any item's visibility changed.
+pub(crate) fn apply_conditions<R>(items: &mut impl MenuItemCollection<R>) -> bool {
+    let mut visibility_changed = false;
+
+    for nth in 0..items.count() {
+        let conditions = items.conditions_of(nth);
+
+        let values = MenuValues {
+            items: &Values {
+                items: &*items,
+                _marker: PhantomData,
+            },
+        };
+        let visible = conditions.visible.map(|visible| visible(&values));
+        let enabled = conditions.enabled.map(|enabled| enabled(&values));
+
+        if let Some(visible) = visible {
+            visibility_changed |= items.set_visible(nth, visible);
+        }
+        if let Some(enabled) = enabled {
+            items.set_enabled(nth, enabled);
+        }
+    }
+
+    visibility_changed
+}
+
 /// Marker trait necessary to avoid a "conflicting implementations" error.
 pub trait Marker {}
 
@@ -22,6 +184,15 @@ pub trait MenuListItem<R>: Marker + View {
     fn value_of(&self) -> R;
 
     fn interact(&mut self) -> R;
+    /// Interacts with the list item, and returns whether its value changed.
+    ///
+    /// The default implementation compares the [stored value](MenuListItem::stored_value) of
+    /// the list item, so it only reports changes of values that can be stored.
+    fn interact_changed(&mut self) -> (R, bool) {
+        let old = self.stored_value();
+        let value = self.interact();
+        (value, self.stored_value() != old)
+    }
 
     fn set_style(&mut self, text_style: &MonoTextStyle<'_, BinaryColor>);
 
@@ -33,6 +204,122 @@ pub trait MenuListItem<R>: Marker + View {
         true
     }
 
+    /// Handles left and right navigation while the list item is selected.
+    ///
+    /// Returns a value if the navigation changed the value of the list item.
+    ///
+    /// Menus with a [`MenuLayout::Grid`](crate::MenuLayout::Grid) or
+    /// [`MenuLayout::Carousel`](crate::MenuLayout::Carousel) layout use left and right navigation
+    /// themselves, and don't pass it to their items.
+    fn step(&mut self, _direction: Direction) -> Option<R> {
+        None
+    }
+
+    /// Returns the title of the list item.
+    fn title(&self) -> &str {
+        ""
+    }
+
+    /// Hides the list item if it doesn't match `filter`. An empty filter matches every item.
+    ///
+    /// Hidden items take up no space and can't be selected.
+    fn filter(&mut self, _filter: &str) {}
+
+    /// Returns whether the list item is displayed.
+    fn is_visible(&self) -> bool {
+        true
+    }
+
+    /// Shows or hides the list item.
+    ///
+    /// Hidden items take up no space and can't be selected. Returns whether the visibility of the
+    /// list item changed, which is never the case if the list item can't be hidden.
+    fn set_visible(&mut self, _visible: bool) -> bool {
+        false
+    }
+
+    /// Returns whether the list item is enabled.
+    ///
+    /// Disabled items are displayed grayed out, and user navigation skips over them.
+    fn is_enabled(&self) -> bool {
+        true
+    }
+
+    /// Enables or disables the list item. Returns whether the list item changed, which is never the
+    /// case if the list item can't be disabled.
+    fn set_enabled(&mut self, _enabled: bool) -> bool {
+        false
+    }
+
+    /// Returns the value of the list item, for [`Condition`]s that compare it by its type.
+    ///
+    /// List items that return `None` are compared by their
+    /// [stored value](MenuListItem::stored_value).
+    fn any_value(&self) -> Option<&dyn Any> {
+        None
+    }
+
+    /// Changes the value of the list item to `value`, if it has the same type as the
+    /// [value](MenuListItem::any_value) of the list item.
+    ///
+    /// Returns `false` if the list item has no value of this type.
+    fn set_any_value(&mut self, _value: &dyn Any) -> bool {
+        false
+    }
+
+    /// Returns the conditions that control whether the list item is visible or enabled.
+    fn conditions(&self) -> Conditions {
+        Conditions::none()
+    }
+
+    /// Returns the question the menu asks before activating the list item, if any.
+    fn confirmation(&self) -> Option<Confirmation> {
+        None
+    }
+
+    /// Returns the part of the list item that the selection indicator highlights, relative to the
+    /// top left corner of the list item.
+    ///
+    /// Returns `None` to highlight the whole list item.
+    fn selection_area(&self) -> Option<Rectangle> {
+        None
+    }
+
+    /// Returns the help text of the list item, if any.
+    ///
+    /// The menu can display the help text of the selected item in a status line, or in a page
+    /// that is opened with [`Action::Help`](crate::interaction::Action::Help).
+    fn help(&self) -> Option<&str> {
+        None
+    }
+
+    /// Returns the identifier of the list item, if it has one.
+    fn id(&self) -> Option<u16> {
+        None
+    }
+
+    /// Loads the value of the list item from `storage`, if the list item is bound to a key.
+    fn load(&mut self, _storage: &dyn SettingsStorage) {}
+
+    /// Writes the value of the list item into `storage`, if the list item is bound to a key.
+    ///
+    /// Returns `false` if `storage` failed to store the value.
+    fn store(&self, _storage: &mut dyn SettingsStorage) -> bool {
+        true
+    }
+
+    /// Returns the value of the list item in its [`SettingsStorage`] representation, if it has one.
+    fn stored_value(&self) -> Option<u32> {
+        None
+    }
+
+    /// Changes the value of the list item to the one represented by `value`.
+    ///
+    /// Returns `false` if the list item has no such value.
+    fn set_stored_value(&mut self, _value: u32) -> bool {
+        false
+    }
+
     fn draw_styled<D>(
         &self,
         text_style: &MonoTextStyle<'static, BinaryColor>,
@@ -42,6 +329,43 @@ pub trait MenuListItem<R>: Marker + View {
         D: DrawTarget<Color = BinaryColor>;
 }
 
+/// Returns whether `text` starts with `prefix`, ignoring ASCII case.
+pub(crate) fn starts_with_ignore_case(text: &str, prefix: &str) -> bool {
+    text.get(..prefix.len())
+        .is_some_and(|start| start.eq_ignore_ascii_case(prefix))
+}
+
+/// Draw target that only draws every other pixel, used to gray out disabled items.
+pub(crate) struct Dimmed<'a, D>(pub &'a mut D);
+
+impl<D> Dimensions for Dimmed<'_, D>
+where
+    D: DrawTarget<Color = BinaryColor>,
+{
+    fn bounding_box(&self) -> Rectangle {
+        self.0.bounding_box()
+    }
+}
+
+impl<D> DrawTarget for Dimmed<'_, D>
+where
+    D: DrawTarget<Color = BinaryColor>,
+{
+    type Color = BinaryColor;
+    type Error = D::Error;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        self.0.draw_iter(
+            pixels
+                .into_iter()
+                .filter(|Pixel(point, _)| (point.x + point.y) % 2 == 0),
+        )
+    }
+}
+
 /// Helper struct to draw a menu line that has a title and some additional marker.
 pub struct MenuLine {
     bounds: Rectangle,
@@ -65,6 +389,11 @@ impl MenuLine {
         }
     }
 
+    /// Changes the height of the line. Titles that don't fit into one line are wrapped.
+    pub(crate) fn set_height(&mut self, height: u32) {
+        self.bounds.size.height = height;
+    }
+
     pub fn empty() -> Self {
         MenuLine {
             bounds: Rectangle::new(Point::zero(), Size::new(1, 0)),
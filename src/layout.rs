@@ -0,0 +1,494 @@
+//! Arrangement and navigation of [`MenuLayout::Grid`] and [`MenuLayout::Carousel`] menus.
+
+use core::ops::Range;
+
+use embedded_graphics::{
+    prelude::{DrawTarget, Point, Primitive, Size},
+    primitives::{Circle, PrimitiveStyle, Rectangle},
+    Drawable,
+};
+use embedded_layout::{
+    layout::linear::LinearLayout,
+    view_group::{EmptyViewGroup, ViewGroup},
+};
+
+use crate::{
+    collection::MenuItemCollection, interaction::Navigation, tabs, theme::Theme, MenuLayout,
+};
+
+/// The diameter of a page dot.
+const DOT_SIZE: u32 = 5;
+/// The space between two page dots.
+const DOT_SPACING: u32 = 3;
+/// The height reserved for the page dots, below the items of a carousel.
+pub(crate) const PAGE_DOTS_HEIGHT: u32 = DOT_SIZE + 2;
+
+/// Arranges the items according to `layout`.
+pub(crate) fn arrange<R>(items: &mut (impl ViewGroup + MenuItemCollection<R>), layout: MenuLayout) {
+    LinearLayout::vertical(EmptyViewGroup).arrange_view_group(items);
+
+    match layout {
+        MenuLayout::List => {}
+        MenuLayout::Grid { columns, spacing } => arrange_grid(items, columns.max(1), spacing),
+        MenuLayout::Carousel { spacing, .. } => arrange_row(items, spacing),
+    }
+}
+
+/// Hidden items take up no space, so they don't occupy a cell either.
+fn occupies_cell<R>(items: &impl MenuItemCollection<R>, nth: usize) -> bool {
+    items.bounds_of(nth).size.height > 0
+}
+
+/// Returns the number of items in `tab` that occupy a cell.
+fn cell_count<R>(items: &impl MenuItemCollection<R>, tab: Range<usize>) -> usize {
+    tab.filter(|&nth| occupies_cell(items, nth)).count()
+}
+
+/// Returns the cell of the `nth` item. Cells are counted from the first item of the tab, which
+/// starts at `first`.
+fn cell_of<R>(items: &impl MenuItemCollection<R>, first: usize, nth: usize) -> usize {
+    (first..nth)
+        .filter(|&nth| occupies_cell(items, nth))
+        .count()
+}
+
+fn arrange_grid<R>(items: &mut impl MenuItemCollection<R>, columns: usize, spacing: u32) {
+    if items.count() == 0 {
+        return;
+    }
+
+    let origin = items.bounds_of(0).top_left;
+
+    let cell = (0..items.count())
+        .filter(|&nth| occupies_cell(items, nth))
+        .fold(Size::zero(), |cell, nth| {
+            cell.component_max(items.bounds_of(nth).size)
+        });
+
+    // Every tab starts in a new row, below the rows of the previous tab
+    let mut first_row = 0;
+    for tab in 0..items.tab_count().max(1) {
+        let tab_origin = origin + Point::new(0, (first_row * (cell.height + spacing)) as i32);
+
+        let mut cell_index = 0;
+        for nth in tabs::range(items, tab) {
+            let bounds = items.bounds_of(nth);
+
+            let target = if occupies_cell(items, nth) {
+                let column = (cell_index % columns) as u32;
+                let row = (cell_index / columns) as u32;
+                cell_index += 1;
+
+                // Center the item horizontally in its cell
+                tab_origin
+                    + Point::new(
+                        (column * (cell.width + spacing) + (cell.width - bounds.size.width) / 2)
+                            as i32,
+                        (row * (cell.height + spacing)) as i32,
+                    )
+            } else {
+                tab_origin
+            };
+
+            items.translate_item(nth, target - bounds.top_left);
+        }
+
+        // `div_ceil` is not available on our MSRV.
+        #[allow(clippy::manual_div_ceil)]
+        let rows = (cell_index + columns - 1) / columns;
+        first_row += rows as u32;
+    }
+}
+
+/// Places the items next to each other, vertically centered in the row.
+fn arrange_row<R>(items: &mut impl MenuItemCollection<R>, spacing: u32) {
+    if items.count() == 0 {
+        return;
+    }
+
+    let origin = items.bounds_of(0).top_left;
+    let row_height = (0..items.count())
+        .map(|nth| items.bounds_of(nth).size.height)
+        .max()
+        .unwrap_or(0);
+
+    let mut left = 0;
+    for nth in 0..items.count() {
+        let bounds = items.bounds_of(nth);
+
+        let target = if occupies_cell(items, nth) {
+            let target =
+                origin + Point::new(left as i32, ((row_height - bounds.size.height) / 2) as i32);
+            left += bounds.size.width + spacing;
+            target
+        } else {
+            origin
+        };
+
+        items.translate_item(nth, target - bounds.top_left);
+    }
+}
+
+/// Returns the item selected by moving from `selected`, or `None` if `navigation` doesn't depend
+/// on the layout.
+///
+/// Only items for which `selectable` returns `true` are selected. If there are none, the selection
+/// doesn't change. Grid navigation stays in `tab`, the range of items in the displayed tab.
+pub(crate) fn navigate<R>(
+    items: &impl MenuItemCollection<R>,
+    selected: usize,
+    tab: Range<usize>,
+    layout: MenuLayout,
+    navigation: Navigation,
+    selectable: impl Fn(usize) -> bool,
+) -> Option<usize> {
+    let step = |navigation: Navigation| {
+        let new = navigation.calculate_selection(selected, items.count(), &selectable);
+        if selectable(new) {
+            new
+        } else {
+            selected
+        }
+    };
+
+    match (layout, navigation) {
+        (MenuLayout::List, _) => None,
+        (MenuLayout::Grid { columns, .. }, _) => {
+            navigate_grid(items, selected, tab, columns, navigation, &selectable)
+        }
+        (MenuLayout::Carousel { .. }, Navigation::Left) => Some(step(Navigation::Previous)),
+        (MenuLayout::Carousel { .. }, Navigation::Right) => Some(step(Navigation::Next)),
+        (MenuLayout::Carousel { .. }, _) => None,
+    }
+}
+
+fn navigate_grid<R>(
+    items: &impl MenuItemCollection<R>,
+    selected: usize,
+    tab: Range<usize>,
+    columns: usize,
+    navigation: Navigation,
+    selectable: impl Fn(usize) -> bool,
+) -> Option<usize> {
+    if !matches!(
+        navigation,
+        Navigation::Previous | Navigation::Next | Navigation::Left | Navigation::Right
+    ) {
+        return None;
+    }
+
+    let columns = columns.max(1);
+    let first = tab.start;
+    let cells = cell_count(items, tab);
+    if cells == 0 || !occupies_cell(items, selected) {
+        return Some(selected);
+    }
+
+    let mut cell = Cell::of(items, first, selected);
+
+    // Skip cells that are not selectable, but give up after visiting every cell, or every cell of
+    // the column, once. `div_ceil` is not available on our MSRV.
+    #[allow(clippy::manual_div_ceil)]
+    let steps = match navigation {
+        Navigation::Left | Navigation::Right => cells,
+        _ => (cells - cell.index % columns + columns - 1) / columns,
+    };
+    for _ in 0..steps {
+        let index = cell.index;
+        let next = match navigation {
+            Navigation::Left => index.checked_sub(1).unwrap_or(cells - 1),
+            Navigation::Right => (index + 1) % cells,
+            Navigation::Previous if index >= columns => index - columns,
+            // Wrap around to the last row that has an item in this column
+            Navigation::Previous => index + (cells - 1 - index) / columns * columns,
+            Navigation::Next if index + columns < cells => index + columns,
+            _ => index % columns,
+        };
+
+        cell.move_to(items, next);
+        if selectable(cell.item) {
+            return Some(cell.item);
+        }
+    }
+
+    Some(selected)
+}
+
+/// An occupied cell of a grid, and the item in it.
+struct Cell {
+    index: usize,
+    item: usize,
+}
+
+impl Cell {
+    fn of<R>(items: &impl MenuItemCollection<R>, first: usize, nth: usize) -> Self {
+        Self {
+            index: cell_of(items, first, nth),
+            item: nth,
+        }
+    }
+
+    /// Moves to the `index`th cell by walking over the items between the two cells, instead of
+    /// counting the cells from the first item.
+    fn move_to<R>(&mut self, items: &impl MenuItemCollection<R>, index: usize) {
+        while self.index < index {
+            self.item += 1;
+            if occupies_cell(items, self.item) {
+                self.index += 1;
+            }
+        }
+        while self.index > index {
+            self.item -= 1;
+            if occupies_cell(items, self.item) {
+                self.index -= 1;
+            }
+        }
+    }
+}
+
+/// Draws a dot for every item of `tab` in the bottom of `area`, filling the dot of the selected
+/// item.
+///
+/// If the dots don't fit in `area`, only the dots around the selected item are drawn.
+pub(crate) fn draw_page_dots<R, C, D>(
+    items: &impl MenuItemCollection<R>,
+    selected: usize,
+    tab: Range<usize>,
+    area: Rectangle,
+    theme: &C,
+    display: &mut D,
+) -> Result<(), D::Error>
+where
+    C: Theme,
+    D: DrawTarget<Color = C::Color>,
+{
+    let first = tab.start;
+    let cells = cell_count(items, tab) as u32;
+    if cells == 0 {
+        return Ok(());
+    }
+
+    let selected = cell_of(items, first, selected) as u32;
+    let max_dots = ((area.size.width + DOT_SPACING) / (DOT_SIZE + DOT_SPACING)).max(1);
+    let dots = cells.min(max_dots);
+    let first = selected.saturating_sub(dots / 2).min(cells - dots);
+
+    let width = dots * (DOT_SIZE + DOT_SPACING) - DOT_SPACING;
+    let left = area.center().x - width as i32 / 2;
+    let top = area.top_left.y + (area.size.height - DOT_SIZE) as i32;
+
+    for dot in 0..dots {
+        let style = if first + dot == selected {
+            PrimitiveStyle::with_fill(theme.text_color())
+        } else {
+            PrimitiveStyle::with_stroke(theme.text_color(), 1)
+        };
+
+        Circle::new(
+            Point::new(left + (dot * (DOT_SIZE + DOT_SPACING)) as i32, top),
+            DOT_SIZE,
+        )
+        .into_styled(style)
+        .draw(display)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use embedded_graphics::{
+        image::ImageRaw, mock_display::MockDisplay, pixelcolor::BinaryColor, prelude::Point,
+        Drawable,
+    };
+
+    use crate::{
+        interaction::{Interaction, Navigation},
+        items::IconItem,
+        Menu, MenuLayout, MenuStyle,
+    };
+
+    #[test]
+    fn grid_layout() {
+        let icon = ImageRaw::<BinaryColor>::new(&[0xAA; 8], 8);
+        let items = ["1", "2", "3", "4", "5", "6", "7"]
+            .map(|caption| IconItem::new(caption, icon, caption.as_bytes()[0] - b'0'));
+
+        let mut menu = Menu::with_style(
+            "Title",
+            MenuStyle::default().with_layout(MenuLayout::Grid {
+                columns: 3,
+                spacing: 2,
+            }),
+        )
+        .add_menu_items(items)
+        .build();
+
+        let mut navigate = |navigation| {
+            menu.interact(Interaction::Navigation(navigation));
+            menu.selected_value()
+        };
+
+        // Moving left and right wraps around to the neighbouring rows
+        assert_eq!(navigate(Navigation::Left), 7);
+        assert_eq!(navigate(Navigation::Right), 1);
+        assert_eq!(navigate(Navigation::Right), 2);
+
+        // Moving up and down wraps around to the same column
+        assert_eq!(navigate(Navigation::Previous), 5);
+        assert_eq!(navigate(Navigation::Next), 2);
+        assert_eq!(navigate(Navigation::Right), 3);
+        assert_eq!(navigate(Navigation::Previous), 6);
+
+        // Cells are 8px wide and 19px tall, the menu is scrolled by rows
+        let display = MockDisplay::<BinaryColor>::new();
+        menu.interact(Interaction::Navigation(Navigation::Beginning));
+        menu.update(&display);
+        assert_eq!(menu.item_at(Point::new(15, 16)), Some(1));
+        assert_eq!(menu.item_at(Point::new(15, 36)), Some(4));
+        assert_eq!(menu.item_at(Point::new(21, 16)), None);
+
+        menu.interact(Interaction::Navigation(Navigation::End));
+        menu.update(&display);
+        assert_eq!(menu.state.list_offset, 8);
+        assert_eq!(menu.item_at(Point::new(5, 45)), Some(6));
+
+        let mut display = MockDisplay::<BinaryColor>::new();
+        display.set_allow_overdraw(true);
+        menu.draw(&mut display).unwrap();
+    }
+
+    #[test]
+    fn grid_tabs_start_in_their_own_row() {
+        let icon = ImageRaw::<BinaryColor>::new(&[0xAA; 8], 8);
+        let item = |value: u8| IconItem::new("", icon, value);
+
+        let mut menu = Menu::with_style(
+            "Title",
+            MenuStyle::default().with_layout(MenuLayout::Grid {
+                columns: 3,
+                spacing: 2,
+            }),
+        )
+        .add_tab("A", item(0))
+        .add_menu_item(item(1))
+        .add_menu_item(item(2))
+        .add_menu_item(item(3))
+        .add_tab("B", item(4))
+        .add_menu_item(item(5))
+        .build();
+
+        let mut navigate = |navigation| {
+            menu.interact(Interaction::Navigation(navigation));
+            menu.selected_value()
+        };
+
+        // Navigation wraps around within the tab
+        assert_eq!(navigate(Navigation::Left), 3);
+        assert_eq!(navigate(Navigation::Right), 0);
+        assert_eq!(navigate(Navigation::Previous), 3);
+
+        assert_eq!(navigate(Navigation::NextTab), 4);
+        assert_eq!(navigate(Navigation::Left), 5);
+        assert_eq!(navigate(Navigation::Right), 4);
+        assert_eq!(navigate(Navigation::Previous), 4);
+        assert_eq!(navigate(Navigation::Next), 4);
+
+        // The first item of the second tab is in the first cell of the first row
+        let display = MockDisplay::<BinaryColor>::new();
+        menu.update(&display);
+        assert_eq!(menu.item_at(Point::new(5, 12)), Some(4));
+        assert_eq!(menu.item_at(Point::new(15, 12)), Some(5));
+        assert_eq!(menu.item_at(Point::new(25, 12)), None);
+        assert_eq!(menu.item_at(Point::new(5, 22)), None);
+
+        menu.select_tab(0);
+        menu.update(&display);
+        assert_eq!(menu.item_at(Point::new(5, 12)), Some(0));
+        assert_eq!(menu.item_at(Point::new(5, 22)), Some(3));
+    }
+
+    #[test]
+    fn carousel_layout() {
+        let icon = ImageRaw::<BinaryColor>::new(&[0xFF; 32], 16);
+        let items = ["A", "B", "C"].map(|caption| IconItem::new(caption, icon, caption));
+
+        let mut menu = Menu::with_style(
+            "Title",
+            MenuStyle::default()
+                .with_layout(MenuLayout::Carousel {
+                    spacing: 4,
+                    page_dots: true,
+                })
+                .with_animated_selection_indicator(2),
+        )
+        .add_menu_items(items)
+        .build();
+
+        let mut navigate = |navigation| {
+            menu.interact(Interaction::Navigation(navigation));
+            menu.selected_value()
+        };
+
+        assert_eq!(navigate(Navigation::Left), "C");
+        assert_eq!(navigate(Navigation::Right), "A");
+        assert_eq!(navigate(Navigation::Next), "B");
+
+        let display = MockDisplay::<BinaryColor>::new();
+        for _ in 0..10 {
+            menu.update(&display);
+        }
+
+        // The selected item is centered, items are 16px wide and 27px tall
+        assert_eq!(menu.state.list_offset, 0);
+        assert_eq!(menu.item_at(Point::new(30, 30)), Some(1));
+        assert_eq!(menu.item_at(Point::new(10, 30)), Some(0));
+        assert_eq!(menu.item_at(Point::new(22, 30)), None);
+        assert_eq!(menu.item_at(Point::new(50, 30)), Some(2));
+
+        // The row slides to the newly selected item
+        menu.interact(Interaction::Navigation(Navigation::Right));
+        menu.update(&display);
+        assert_eq!(menu.item_at(Point::new(47, 30)), Some(2));
+        for _ in 0..10 {
+            menu.update(&display);
+        }
+        assert_eq!(menu.item_at(Point::new(47, 30)), None);
+        assert_eq!(menu.item_at(Point::new(30, 30)), Some(2));
+
+        let mut display = MockDisplay::<BinaryColor>::new();
+        display.set_allow_overdraw(true);
+        menu.draw(&mut display).unwrap();
+
+        // The dot of the selected item is filled
+        assert_eq!(display.get_pixel(Point::new(40, 61)), Some(BinaryColor::On));
+        assert_eq!(display.get_pixel(Point::new(24, 61)), None);
+    }
+
+    #[test]
+    fn page_dots_fit_the_display() {
+        let icon = ImageRaw::<BinaryColor>::new(&[0xFF; 32], 16);
+        let items = [(); 20].map(|_| IconItem::new("", icon, ()));
+
+        let mut menu = Menu::with_style(
+            "Title",
+            MenuStyle::default().with_layout(MenuLayout::Carousel {
+                spacing: 4,
+                page_dots: true,
+            }),
+        )
+        .add_menu_items(items)
+        .build();
+
+        menu.interact(Interaction::Navigation(Navigation::End));
+
+        let mut display = MockDisplay::<BinaryColor>::new();
+        display.set_allow_overdraw(true);
+        menu.update(&display);
+        menu.draw(&mut display).unwrap();
+
+        // Only 8 dots fit, the last one belongs to the selected item
+        assert_eq!(display.get_pixel(Point::new(60, 61)), Some(BinaryColor::On));
+        assert_eq!(display.get_pixel(Point::new(4, 61)), None);
+    }
+}
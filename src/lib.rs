@@ -1,43 +1,64 @@
 #![cfg_attr(not(test), no_std)]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
 pub mod adapters;
 pub mod builder;
 pub mod collection;
+pub mod event;
+pub mod footer;
+pub mod header;
+pub mod help;
 pub mod interaction;
 pub mod items;
+mod layout;
 pub mod margin;
+pub mod overlay;
 pub mod selection_indicator;
+#[cfg(feature = "snapshot")]
+pub mod snapshot;
+pub mod storage;
+pub mod tabs;
+mod text;
 pub mod theme;
 
 use crate::{
     builder::MenuBuilder,
     collection::MenuItemCollection,
+    event::MenuEvent,
+    footer::{FooterContext, MenuMode, SoftKeys},
+    header::{HeaderContext, HeaderStyle, TitleBar},
+    help::HelpPage,
     interaction::{
-        programmed::Programmed, Action, InputAdapter, InputAdapterSource, InputResult, InputState,
-        Interaction, Navigation,
+        programmed::Programmed, recorder::Event, Action, Direction, InputAdapter,
+        InputAdapterSource, InputResult, InputState, Interaction, Navigation,
     },
+    items::{apply_conditions, menu_item::SelectValue, starts_with_ignore_case, Confirmation},
+    overlay::{Dialog, Overlay},
     selection_indicator::{
         style::{line::Line as LineIndicator, IndicatorStyle},
         AnimatedPosition, Indicator, SelectionIndicatorController, State as IndicatorState,
         StaticPosition,
     },
+    storage::SettingsStorage,
+    tabs::MenuTabs,
     theme::Theme,
 };
-use core::marker::PhantomData;
+use core::{
+    fmt::{self, Debug, Formatter},
+    marker::PhantomData,
+};
 use embedded_graphics::{
     draw_target::DrawTarget,
-    geometry::{AnchorPoint, AnchorX, AnchorY},
+    geometry::{AnchorX, AnchorY},
     mono_font::{ascii::FONT_6X10, MonoFont, MonoTextStyle},
     pixelcolor::BinaryColor,
-    prelude::{Dimensions, DrawTargetExt, Point},
+    prelude::{Dimensions, DrawTargetExt, Point, Size},
     primitives::{Line, Primitive, PrimitiveStyle, Rectangle},
     Drawable,
 };
-use embedded_layout::{layout::linear::LinearLayout, prelude::*, view_group::ViewGroup};
-use embedded_text::{
-    style::{HeightMode, TextBoxStyle},
-    TextBox,
-};
+use embedded_layout::{prelude::*, view_group::ViewGroup};
 
 pub use embedded_menu_macros::SelectValue;
 
@@ -48,14 +69,68 @@ pub enum DisplayScrollbar {
     Auto,
 }
 
+/// How a menu arranges its items.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum MenuLayout {
+    /// Items are stacked below each other.
+    List,
+    /// Items are arranged in rows of `columns` equally sized cells, `spacing` pixels apart, e.g.
+    /// to display [`IconItem`](crate::items::IconItem)s.
+    ///
+    /// Only items that have their own width, like [`IconItem`](crate::items::IconItem), can be
+    /// arranged in a grid. Lines like [`MenuItem`](crate::items::MenuItem)s and section titles
+    /// are drawn across the whole display, so they overlap in a grid.
+    ///
+    /// [`Navigation::Previous`] and [`Navigation::Next`] move between rows, and
+    /// [`Navigation::Left`] and [`Navigation::Right`] between columns, wrapping around at the
+    /// edges. The menu scrolls by rows. [`VirtualItems`](crate::collection::VirtualItems) are not
+    /// supported in a grid. In a tabbed menu, navigation stays in the displayed tab, and only
+    /// [`Navigation::PreviousTab`] and [`Navigation::NextTab`] switch between the tabs.
+    Grid { columns: usize, spacing: u32 },
+    /// Items are arranged in a single row, `spacing` pixels apart. The selected item is displayed
+    /// in the center, with its neighbours peeking in from the sides.
+    ///
+    /// Like in a [`MenuLayout::Grid`], only items that have their own width can be arranged in a
+    /// carousel, and [`VirtualItems`](crate::collection::VirtualItems) are not supported.
+    ///
+    /// The row slides to the selected item, animated by the menu's
+    /// [`SelectionIndicatorController`]. Every navigation moves between the items of the displayed
+    /// tab, except [`Navigation::PreviousTab`] and [`Navigation::NextTab`]. If `page_dots`
+    /// is set, a dot is displayed for every item below the row. If there are more items than dots
+    /// fit on the display, only the dots around the selected item are displayed.
+    Carousel { spacing: u32, page_dots: bool },
+}
+
+/// What a menu does after it hasn't received input for a while.
+///
+/// See [`MenuStyle::with_idle_timeout`].
+#[derive(Copy, Clone, Debug)]
+pub enum IdleAction<R> {
+    /// Select the first selectable item.
+    Reset,
+    /// Return a clone of the value from [`Menu::poll_idle`].
+    Return(R),
+}
+
 #[derive(Copy, Clone, Debug)]
-pub struct MenuStyle<S, IT, P, R, T> {
+pub(crate) struct IdleTimeout<R> {
+    pub ticks: u32,
+    pub action: IdleAction<R>,
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct MenuStyle<S, IT, P, R, T, H = TitleBar> {
     pub(crate) theme: T,
     pub(crate) scrollbar: DisplayScrollbar,
     pub(crate) font: &'static MonoFont<'static>,
     pub(crate) title_font: &'static MonoFont<'static>,
     pub(crate) input_adapter: IT,
     pub(crate) indicator: Indicator<P, S>,
+    pub(crate) layout: MenuLayout,
+    pub(crate) header: H,
+    pub(crate) footer: Option<SoftKeys>,
+    pub(crate) help_line: bool,
+    pub(crate) idle_timeout: Option<IdleTimeout<R>>,
     _marker: PhantomData<R>,
 }
 
@@ -80,34 +155,94 @@ where
                 style: LineIndicator,
                 controller: StaticPosition,
             },
+            layout: MenuLayout::List,
+            header: TitleBar::new(),
+            footer: None,
+            help_line: false,
+            idle_timeout: None,
             _marker: PhantomData,
         }
     }
 }
 
-impl<S, IT, P, R, T> MenuStyle<S, IT, P, R, T>
+impl<S, IT, P, R, T, H> MenuStyle<S, IT, P, R, T, H>
 where
     S: IndicatorStyle,
     IT: InputAdapterSource<R>,
     P: SelectionIndicatorController,
     T: Theme,
+    H: HeaderStyle,
 {
-    pub const fn with_font(self, font: &'static MonoFont<'static>) -> Self {
+    pub fn with_font(self, font: &'static MonoFont<'static>) -> Self {
         Self { font, ..self }
     }
 
-    pub const fn with_title_font(self, title_font: &'static MonoFont<'static>) -> Self {
+    pub fn with_title_font(self, title_font: &'static MonoFont<'static>) -> Self {
         Self { title_font, ..self }
     }
 
-    pub const fn with_scrollbar_style(self, scrollbar: DisplayScrollbar) -> Self {
+    pub fn with_scrollbar_style(self, scrollbar: DisplayScrollbar) -> Self {
         Self { scrollbar, ..self }
     }
 
-    pub const fn with_selection_indicator<S2>(
-        self,
-        indicator_style: S2,
-    ) -> MenuStyle<S2, IT, P, R, T>
+    /// Sets how the menu arranges its items.
+    pub fn with_layout(self, layout: MenuLayout) -> Self {
+        Self { layout, ..self }
+    }
+
+    /// Displays a soft-key bar below the items, with the labels returned by `soft_keys`.
+    ///
+    /// The labels are requested every time the menu is updated or drawn, so they can change with
+    /// the selected item and with what the user is doing. Returning no labels hides the bar.
+    pub fn with_footer(self, soft_keys: SoftKeys) -> Self {
+        Self {
+            footer: Some(soft_keys),
+            ..self
+        }
+    }
+
+    /// Displays the first line of the selected item's help text in a status line below the
+    /// items. See [`MenuListItem::help`](items::MenuListItem::help).
+    pub fn with_help_line(self) -> Self {
+        Self {
+            help_line: true,
+            ..self
+        }
+    }
+
+    /// Makes the menu perform `action` after [`Menu::update`] was called `ticks` times without the
+    /// input adapter reporting any input.
+    ///
+    /// The timeout is paused while an overlay, a confirmation dialog or a help page is displayed.
+    pub fn with_idle_timeout(self, ticks: u32, action: IdleAction<R>) -> Self {
+        Self {
+            idle_timeout: Some(IdleTimeout { ticks, action }),
+            ..self
+        }
+    }
+
+    /// Sets how the header of the menu is drawn. See [`TitleBar`] for the default header.
+    pub fn with_header<H2>(self, header: H2) -> MenuStyle<S, IT, P, R, T, H2>
+    where
+        H2: HeaderStyle,
+    {
+        MenuStyle {
+            theme: self.theme,
+            input_adapter: self.input_adapter,
+            scrollbar: self.scrollbar,
+            font: self.font,
+            title_font: self.title_font,
+            indicator: self.indicator,
+            layout: self.layout,
+            header,
+            footer: self.footer,
+            help_line: self.help_line,
+            idle_timeout: self.idle_timeout,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn with_selection_indicator<S2>(self, indicator_style: S2) -> MenuStyle<S2, IT, P, R, T, H>
     where
         S2: IndicatorStyle,
     {
@@ -121,11 +256,16 @@ where
                 style: indicator_style,
                 controller: self.indicator.controller,
             },
+            layout: self.layout,
+            header: self.header,
+            footer: self.footer,
+            help_line: self.help_line,
+            idle_timeout: self.idle_timeout,
             _marker: PhantomData,
         }
     }
 
-    pub const fn with_input_adapter<IT2>(self, input_adapter: IT2) -> MenuStyle<S, IT2, P, R, T>
+    pub fn with_input_adapter<IT2>(self, input_adapter: IT2) -> MenuStyle<S, IT2, P, R, T, H>
     where
         IT2: InputAdapterSource<R>,
     {
@@ -136,14 +276,19 @@ where
             font: self.font,
             title_font: self.title_font,
             indicator: self.indicator,
+            layout: self.layout,
+            header: self.header,
+            footer: self.footer,
+            help_line: self.help_line,
+            idle_timeout: self.idle_timeout,
             _marker: PhantomData,
         }
     }
 
-    pub const fn with_animated_selection_indicator(
+    pub fn with_animated_selection_indicator(
         self,
         frames: i32,
-    ) -> MenuStyle<S, IT, AnimatedPosition, R, T> {
+    ) -> MenuStyle<S, IT, AnimatedPosition, R, T, H> {
         MenuStyle {
             theme: self.theme,
             input_adapter: self.input_adapter,
@@ -154,6 +299,11 @@ where
                 style: self.indicator.style,
                 controller: AnimatedPosition::new(frames),
             },
+            layout: self.layout,
+            header: self.header,
+            footer: self.footer,
+            help_line: self.help_line,
+            idle_timeout: self.idle_timeout,
             _marker: PhantomData,
         }
     }
@@ -176,6 +326,8 @@ where
     S: IndicatorStyle,
 {
     selected: usize,
+    /// The identifier of the selected item, if it has one.
+    selected_id: Option<u16>,
     list_offset: i32,
     interaction_state: IT::State,
     indicator_state: IndicatorState<P, S>,
@@ -191,6 +343,7 @@ where
     fn default() -> Self {
         Self {
             selected: 0,
+            selected_id: None,
             list_offset: Default::default(),
             interaction_state: Default::default(),
             indicator_state: Default::default(),
@@ -218,6 +371,46 @@ where
 {
 }
 
+impl<IT, P, S> PartialEq for MenuState<IT, P, S>
+where
+    IT: InputAdapter,
+    IT::State: PartialEq,
+    P: SelectionIndicatorController,
+    P::State: PartialEq,
+    S: IndicatorStyle,
+    S::State: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.selected == other.selected
+            && self.selected_id == other.selected_id
+            && self.list_offset == other.list_offset
+            && self.interaction_state == other.interaction_state
+            && self.indicator_state == other.indicator_state
+            && self.last_input_state == other.last_input_state
+    }
+}
+
+impl<IT, P, S> Debug for MenuState<IT, P, S>
+where
+    IT: InputAdapter,
+    IT::State: Debug,
+    P: SelectionIndicatorController,
+    P::State: Debug,
+    S: IndicatorStyle,
+    S::State: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MenuState")
+            .field("selected", &self.selected)
+            .field("selected_id", &self.selected_id)
+            .field("list_offset", &self.list_offset)
+            .field("interaction_state", &self.interaction_state)
+            .field("indicator_state", &self.indicator_state)
+            .field("last_input_state", &self.last_input_state)
+            .finish()
+    }
+}
+
 impl<IT, P, S> MenuState<IT, P, S>
 where
     IT: InputAdapter,
@@ -228,11 +421,11 @@ where
         self.interaction_state = Default::default();
     }
 
-    fn set_selected_item<ITS, R, T>(
+    fn set_selected_item<ITS, R, T, H>(
         &mut self,
         selected: usize,
         items: &impl MenuItemCollection<R>,
-        style: &MenuStyle<S, ITS, P, R, T>,
+        style: &MenuStyle<S, ITS, P, R, T, H>,
     ) where
         ITS: InputAdapterSource<R, InputAdapter = IT>,
         T: Theme,
@@ -241,8 +434,14 @@ where
             Navigation::JumpTo(selected)
                 .calculate_selection(self.selected, items.count(), |i| items.selectable(i));
         self.selected = selected;
+        self.selected_id = items.id_of(selected);
 
-        let selected_offset = items.bounds_of(selected).top_left.y;
+        // Carousels slide horizontally
+        let bounds = items.bounds_of(selected);
+        let selected_offset = match style.layout {
+            MenuLayout::Carousel { .. } => bounds.top_left.x,
+            MenuLayout::List | MenuLayout::Grid { .. } => bounds.top_left.y,
+        };
 
         style
             .indicator
@@ -250,19 +449,186 @@ where
     }
 }
 
-pub struct Menu<T, IT, VG, R, P, S, C>
+impl<IT, P, S> MenuState<IT, P, S>
+where
+    IT: InputAdapter,
+    P: SelectionIndicatorController,
+    S: IndicatorStyle,
+{
+    /// Returns the parts of the state that should be persisted, e.g. across reboots.
+    pub fn saved(&self) -> SavedState {
+        SavedState {
+            selected: self.selected,
+            selected_id: self.selected_id,
+            list_offset: self.list_offset,
+        }
+    }
+}
+
+impl<IT, P, S> From<SavedState> for MenuState<IT, P, S>
+where
+    IT: InputAdapter,
+    P: SelectionIndicatorController,
+    S: IndicatorStyle,
+{
+    fn from(saved: SavedState) -> Self {
+        Self {
+            selected: saved.selected,
+            selected_id: saved.selected_id,
+            list_offset: saved.list_offset,
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<IT, P, S> serde::Serialize for MenuState<IT, P, S>
+where
+    IT: InputAdapter,
+    P: SelectionIndicatorController,
+    S: IndicatorStyle,
+{
+    fn serialize<SE>(&self, serializer: SE) -> Result<SE::Ok, SE::Error>
+    where
+        SE: serde::Serializer,
+    {
+        self.saved().serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, IT, P, S> serde::Deserialize<'de> for MenuState<IT, P, S>
+where
+    IT: InputAdapter,
+    P: SelectionIndicatorController,
+    S: IndicatorStyle,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        SavedState::deserialize(deserializer).map(Self::from)
+    }
+}
+
+/// The persistent parts of a [`MenuState`].
+///
+/// The saved state can be restored using [`MenuBuilder::build_with_saved_state`]. The selection
+/// is restored by the identifier of the selected item if it has one. Otherwise, or if no item has
+/// the identifier, the selection is restored by index. If the menu has fewer items than the one the
+/// state was saved from, the selection is clamped to the last item.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SavedState {
+    /// The index of the selected item.
+    pub selected: usize,
+    /// The identifier of the selected item, if it has one.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub selected_id: Option<u16>,
+    /// The scroll position of the menu list, in pixels.
+    pub list_offset: i32,
+}
+
+impl SavedState {
+    const VERSION: u8 = 3;
+
+    /// The number of bytes produced by [`SavedState::to_bytes`].
+    pub const SERIALIZED_SIZE: usize = 12;
+
+    /// Serializes the state into a compact, versioned byte representation.
+    ///
+    /// Selection indices that don't fit into 32 bits are saturated, which restores the selection
+    /// to the last item.
+    pub fn to_bytes(&self) -> [u8; Self::SERIALIZED_SIZE] {
+        let selected = u32::try_from(self.selected)
+            .unwrap_or(u32::MAX)
+            .to_le_bytes();
+        let list_offset = self.list_offset.to_le_bytes();
+        let id = self.selected_id.unwrap_or(0).to_le_bytes();
+
+        [
+            Self::VERSION,
+            selected[0],
+            selected[1],
+            selected[2],
+            selected[3],
+            list_offset[0],
+            list_offset[1],
+            list_offset[2],
+            list_offset[3],
+            self.selected_id.is_some() as u8,
+            id[0],
+            id[1],
+        ]
+    }
+
+    /// Deserializes a state created by [`SavedState::to_bytes`].
+    ///
+    /// Data created by previous versions is accepted. Returns `None` if the data is too short or
+    /// was created by an unknown version.
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let (selected, selected_id, [o0, o1, o2, o3]) = match *bytes {
+            [1, s0, s1, o0, o1, o2, o3, ..] => {
+                (u16::from_le_bytes([s0, s1]) as u32, None, [o0, o1, o2, o3])
+            }
+            [2, s0, s1, o0, o1, o2, o3, has_id @ (0 | 1), i0, i1, ..] => (
+                u16::from_le_bytes([s0, s1]) as u32,
+                (has_id == 1).then_some(u16::from_le_bytes([i0, i1])),
+                [o0, o1, o2, o3],
+            ),
+            [Self::VERSION, s0, s1, s2, s3, o0, o1, o2, o3, has_id @ (0 | 1), i0, i1, ..] => (
+                u32::from_le_bytes([s0, s1, s2, s3]),
+                (has_id == 1).then_some(u16::from_le_bytes([i0, i1])),
+                [o0, o1, o2, o3],
+            ),
+            _ => return None,
+        };
+
+        Some(Self {
+            selected: match selected {
+                u32::MAX => usize::MAX,
+                selected => selected as usize,
+            },
+            selected_id,
+            list_offset: i32::from_le_bytes([o0, o1, o2, o3]),
+        })
+    }
+}
+
+pub struct Menu<T, IT, VG, R, P, S, C, H = TitleBar, E = fn(MenuEvent), ST = ()>
 where
     T: AsRef<str>,
     IT: InputAdapterSource<R>,
     P: SelectionIndicatorController,
     S: IndicatorStyle,
     C: Theme,
+    H: HeaderStyle,
 {
     _return_type: PhantomData<R>,
     title: T,
     items: VG,
-    style: MenuStyle<S, IT, P, R, C>,
+    style: MenuStyle<S, IT, P, R, C, H>,
+    event_handler: E,
+    storage: ST,
     state: MenuState<IT::InputAdapter, P, S>,
+    /// The display area the menu was last updated with.
+    display_area: Rectangle,
+    /// Set when item visibility changed outside of [`Menu::update`].
+    needs_layout: bool,
+    /// The number of updates since the last input.
+    idle_ticks: u32,
+    /// Set when the menu timed out, until the input adapter reports input.
+    timed_out: bool,
+    /// Set when the menu timed out with [`IdleAction::Return`], until [`Menu::poll_idle`] is called.
+    idle_return: bool,
+    /// A message displayed over the menu.
+    overlay: Option<Overlay>,
+    /// The confirmation dialog of the item being activated.
+    dialog: Option<Dialog>,
+    /// The help text displayed in place of the menu.
+    help: Option<HelpPage>,
+    /// The index of the displayed tab.
+    current_tab: usize,
 }
 
 impl<T, R, S, C> Menu<T, Programmed, NoItems, R, StaticPosition, S, C>
@@ -280,24 +646,25 @@ where
     }
 }
 
-impl<T, IT, R, P, S, C> Menu<T, IT, NoItems, R, P, S, C>
+impl<T, IT, R, P, S, C, H> Menu<T, IT, NoItems, R, P, S, C, H>
 where
     T: AsRef<str>,
     S: IndicatorStyle,
     IT: InputAdapterSource<R>,
     P: SelectionIndicatorController,
     C: Theme,
+    H: HeaderStyle,
 {
     /// Creates a new menu builder with the given title and style.
     pub fn with_style(
         title: T,
-        style: MenuStyle<S, IT, P, R, C>,
-    ) -> MenuBuilder<T, IT, NoItems, R, P, S, C> {
+        style: MenuStyle<S, IT, P, R, C, H>,
+    ) -> MenuBuilder<T, IT, NoItems, R, P, S, C, H> {
         MenuBuilder::new(title, style)
     }
 }
 
-impl<T, IT, VG, R, P, S, C> Menu<T, IT, VG, R, P, S, C>
+impl<T, IT, VG, R, P, S, C, H, E, ST> Menu<T, IT, VG, R, P, S, C, H, E, ST>
 where
     T: AsRef<str>,
     IT: InputAdapterSource<R>,
@@ -305,6 +672,9 @@ where
     P: SelectionIndicatorController,
     S: IndicatorStyle,
     C: Theme,
+    H: HeaderStyle,
+    E: FnMut(MenuEvent),
+    ST: SettingsStorage,
 {
     pub fn interact(&mut self, input: <IT::InputAdapter as InputAdapter>::Input) -> Option<R> {
         let input = self
@@ -313,195 +683,681 @@ where
             .adapter()
             .handle_input(&mut self.state.interaction_state, input);
 
+        if !matches!(input, InputResult::StateUpdate(InputState::Idle)) {
+            self.idle_ticks = 0;
+            self.timed_out = false;
+        }
+
+        if self.overlay.is_some() {
+            // The overlay captures input, and is closed by any interaction.
+            if let InputResult::Interaction(_) = input {
+                self.overlay = None;
+            }
+            return None;
+        }
+
         self.state.last_input_state = match input {
             InputResult::Interaction(_) => InputState::Idle,
             InputResult::StateUpdate(state) => state,
         };
 
+        if self.dialog.is_some() {
+            return self.interact_with_dialog(input);
+        }
+
+        if self.help.is_some() {
+            self.interact_with_help(input);
+            return None;
+        }
+
+        // Grid and carousel layouts use left and right navigation to move between the items.
+        if let InputResult::Interaction(Interaction::Navigation(navigation)) = input {
+            if let Some(new) = layout::navigate(
+                &self.items,
+                self.state.selected,
+                tabs::range(&self.items, self.current_tab),
+                self.style.layout,
+                navigation,
+                |i| self.selectable(i),
+            ) {
+                self.select(new);
+                return None;
+            }
+        }
+
         match input {
             InputResult::Interaction(interaction) => match interaction {
+                Interaction::Navigation(Navigation::PreviousTab) => {
+                    let count = self.items.tab_count();
+                    if count > 0 {
+                        self.select_tab((self.current_tab + count - 1) % count);
+                    }
+                    None
+                }
+                Interaction::Navigation(Navigation::NextTab) => {
+                    let count = self.items.tab_count();
+                    if count > 0 {
+                        self.select_tab((self.current_tab + 1) % count);
+                    }
+                    None
+                }
+                Interaction::Navigation(Navigation::Left) => self.step(Direction::Left),
+                Interaction::Navigation(Navigation::Right) => self.step(Direction::Right),
+                Interaction::Navigation(Navigation::JumpToLetter(letter)) => {
+                    let mut buffer = [0; 4];
+                    let letter = letter.encode_utf8(&mut buffer);
+                    let matches = |i| {
+                        self.selectable(i)
+                            && starts_with_ignore_case(self.items.title_of(i), letter)
+                    };
+
+                    let next = Navigation::Next.calculate_selection(
+                        self.state.selected,
+                        self.items.count(),
+                        matches,
+                    );
+                    if matches(next) {
+                        self.select(next);
+                    }
+                    None
+                }
                 Interaction::Navigation(navigation) => {
                     let count = self.items.count();
                     let new_selected =
                         navigation.calculate_selection(self.state.selected, count, |i| {
-                            self.items.selectable(i)
+                            self.selectable(i)
                         });
-                    if new_selected != self.state.selected {
-                        self.state
-                            .set_selected_item(new_selected, &self.items, &self.style);
-                    }
+                    self.select(new_selected);
                     None
                 }
-                Interaction::Action(Action::Select) => {
-                    let value = self.items.interact_with(self.state.selected);
+                Interaction::Action(Action::Select) => self.activate(self.state.selected),
+                Interaction::Action(Action::Return(value)) => {
+                    self.emit(MenuEvent::Exited);
                     Some(value)
                 }
-                Interaction::Action(Action::Return(value)) => Some(value),
+                Interaction::Action(Action::Help) => {
+                    self.show_help();
+                    None
+                }
+                Interaction::Click(point) => {
+                    let clicked = self.item_at(point)?;
+                    if !self.selectable(clicked) {
+                        None
+                    } else if clicked == self.state.selected {
+                        self.activate(clicked)
+                    } else {
+                        self.select(clicked);
+                        None
+                    }
+                }
             },
-            _ => None,
+            InputResult::StateUpdate(_) => None,
         }
     }
 
-    pub fn state(&self) -> MenuState<IT::InputAdapter, P, S> {
-        self.state
+    fn emit(&mut self, event: MenuEvent) {
+        (self.event_handler)(event);
     }
-}
 
-impl<T, IT, VG, R, P, S, C> Menu<T, IT, VG, R, P, S, C>
-where
-    T: AsRef<str>,
-    R: Copy,
-    IT: InputAdapterSource<R>,
-    VG: MenuItemCollection<R>,
-    C: Theme,
-    P: SelectionIndicatorController,
-    S: IndicatorStyle,
-{
-    pub fn selected_value(&self) -> R {
-        self.items.value_of(self.state.selected)
+    /// Returns whether the `nth` item exists and is selectable in the displayed tab.
+    fn selectable(&self, nth: usize) -> bool {
+        nth < self.items.count()
+            && self.items.selectable(nth)
+            && tabs::displays(&self.items, self.current_tab, nth)
     }
-}
-
-impl<T, IT, VG, R, C, P, S> Menu<T, IT, VG, R, P, S, C>
-where
-    T: AsRef<str>,
-    IT: InputAdapterSource<R>,
-    VG: ViewGroup + MenuItemCollection<R>,
-    P: SelectionIndicatorController,
-    S: IndicatorStyle,
-    C: Theme,
-{
-    fn header<'t>(
-        &self,
-        title: &'t str,
-        display_area: Rectangle,
-    ) -> Option<impl View + 't + Drawable<Color = C::Color>>
-    where
-        C: Theme + 't,
-    {
-        if title.is_empty() {
-            return None;
-        }
 
-        let text_style = self.style.title_style();
-        let thin_stroke = PrimitiveStyle::with_stroke(self.style.theme.text_color(), 1);
-        let header = LinearLayout::vertical(
-            Chain::new(TextBox::with_textbox_style(
-                title,
-                display_area,
-                text_style,
-                TextBoxStyle::with_height_mode(HeightMode::FitToText),
-            ))
-            .append(
-                // Bottom border
-                Line::new(
-                    display_area.top_left,
-                    display_area.anchor_point(AnchorPoint::TopRight),
-                )
-                .into_styled(thin_stroke),
-            ),
-        )
-        .arrange();
-
-        Some(header)
+    /// Returns the index of the displayed tab. Menus without tabs always display the `0`th tab.
+    pub fn current_tab(&self) -> usize {
+        self.current_tab
     }
 
-    fn top_offset(&self) -> i32 {
-        self.style.indicator.offset(&self.state.indicator_state) - self.state.list_offset
-    }
+    /// Displays the `nth` tab, restoring its selected item and scroll position.
+    ///
+    /// The first selectable item of the tab is selected when the tab is displayed for the first
+    /// time. Does nothing if the menu has no `nth` tab.
+    pub fn select_tab(&mut self, nth: usize) {
+        let old = self.current_tab;
+        if nth >= self.items.tab_count() || nth == old {
+            return;
+        }
 
-    pub fn update(&mut self, display: &impl Dimensions) {
-        // animations
+        let restored = tabs::switch(
+            &mut self.items,
+            old,
+            nth,
+            self.state.selected,
+            self.state.list_offset,
+        );
+        self.current_tab = nth;
+        let (selected, list_offset) = restored.unwrap_or_else(|| {
+            let first = Navigation::Beginning.calculate_selection(
+                self.state.selected,
+                self.items.count(),
+                |i| self.selectable(i),
+            );
+            (first, tabs::extent(&self.items, nth).0)
+        });
+
+        self.state.list_offset = list_offset;
+        self.state
+            .set_selected_item(selected, &self.items, &self.style);
         self.style
             .indicator
-            .update(self.state.last_input_state, &mut self.state.indicator_state);
+            .jump_to_target(&mut self.state.indicator_state);
 
-        // Ensure selection indicator is always visible by moving the menu list.
-        let top_distance = self.top_offset();
+        self.emit(MenuEvent::TabChanged { old, new: nth });
+    }
 
-        let list_offset_change = if top_distance > 0 {
-            let display_area = display.bounding_box();
-            let display_height = display_area.size().height as i32;
+    fn select(&mut self, new: usize) {
+        let old = self.state.selected;
+        if new != old {
+            self.state.set_selected_item(new, &self.items, &self.style);
+            self.emit(MenuEvent::SelectionChanged { old, new });
+        }
+    }
 
-            let header_height = if let Some(header) = self.header(self.title.as_ref(), display_area)
-            {
-                header.size().height as i32
-            } else {
-                0
-            };
+    /// Handles input while a confirmation dialog is open.
+    fn interact_with_dialog(&mut self, input: InputResult<R>) -> Option<R> {
+        let dialog = self.dialog.as_mut()?;
+        let confirmation = self.items.confirmation_of(dialog.index)?;
 
-            let selected_height = MenuItemCollection::bounds_of(&self.items, self.state.selected)
-                .size()
-                .height as i32;
-            let indicator_height = self
-                .style
-                .indicator
-                .item_height(selected_height, &self.state.indicator_state);
+        match input {
+            InputResult::Interaction(Interaction::Navigation(
+                Navigation::Previous | Navigation::Next | Navigation::Left | Navigation::Right,
+            )) => {
+                dialog.confirm_selected = !dialog.confirm_selected;
+                dialog.held = 0;
+                None
+            }
+            InputResult::Interaction(Interaction::Action(Action::Select))
+                if !dialog.confirm_selected =>
+            {
+                self.dialog = None;
+                None
+            }
+            InputResult::Interaction(Interaction::Action(Action::Select))
+            | InputResult::StateUpdate(InputState::InProgress(_)) => {
+                dialog.selected |= matches!(input, InputResult::Interaction(_));
+                if dialog.is_confirmed(&confirmation) {
+                    let index = dialog.index;
+                    self.dialog = None;
+                    Some(self.commit(index))
+                } else {
+                    None
+                }
+            }
+            InputResult::Interaction(Interaction::Action(Action::Return(_))) => {
+                self.dialog = None;
+                None
+            }
+            _ => None,
+        }
+    }
 
-            // Indicator is below display top. We only have to
-            // move if indicator bottom is below display bottom.
-            (top_distance + indicator_height + header_height - display_height).max(0)
-        } else {
-            // We need to move up
-            top_distance
+    /// Handles input while a help text is displayed. Navigation scrolls the text, and any other
+    /// interaction closes it.
+    fn interact_with_help(&mut self, input: InputResult<R>) {
+        let area = self.help_page_area(self.display_area);
+        let Some(page) = self.help.as_mut() else {
+            return;
         };
 
-        // Move menu list.
-        self.state.list_offset += list_offset_change;
+        match input {
+            InputResult::Interaction(Interaction::Navigation(navigation)) => {
+                let help = self.items.help_of(page.index).unwrap_or("");
+                page.navigate(navigation, help, area, self.style.font);
+            }
+            InputResult::Interaction(_) => self.help = None,
+            InputResult::StateUpdate(_) => {}
+        }
     }
-}
 
-impl<T, IT, VG, R, C, P, S> Drawable for Menu<T, IT, VG, R, P, S, C>
-where
-    T: AsRef<str>,
-    IT: InputAdapterSource<R>,
-    VG: ViewGroup + MenuItemCollection<R>,
-    P: SelectionIndicatorController,
-    S: IndicatorStyle,
-    C: Theme,
-{
-    type Color = C::Color;
-    type Output = ();
+    /// Activates an item, or opens its confirmation dialog.
+    ///
+    /// Items that can't be selected, e.g. because a filter hides every item, are not activated.
+    fn activate(&mut self, index: usize) -> Option<R> {
+        if !self.selectable(index) {
+            return None;
+        }
 
-    fn draw<D>(&self, display: &mut D) -> Result<(), D::Error>
-    where
-        D: DrawTarget<Color = C::Color>,
-    {
-        let display_area = display.bounding_box();
+        if self.items.confirmation_of(index).is_some() {
+            self.dialog = Some(Dialog::new(index));
+            self.emit(MenuEvent::ConfirmationRequested { index });
+            return None;
+        }
 
-        let header = self.header(self.title.as_ref(), display_area);
-        let content_area = if let Some(header) = header {
-            header.draw(display)?;
-            display_area.resized_height(
-                display_area.size().height - header.size().height,
-                AnchorY::Bottom,
-            )
-        } else {
-            display_area
-        };
+        Some(self.commit(index))
+    }
 
-        let menu_height = content_area.size().height as i32;
-        let list_height = self.items.bounds().size().height as i32;
+    fn commit(&mut self, index: usize) -> R {
+        let (value, changed) = self.items.interact_changed(index);
+        if changed {
+            self.emit(MenuEvent::ValueChanged { index });
+        }
+        self.emit(MenuEvent::EditCommitted { index });
+        self.store_setting(index);
+        self.apply_conditions();
+        value
+    }
 
-        let draw_scrollbar = match self.style.scrollbar {
-            DisplayScrollbar::Display => true,
-            DisplayScrollbar::Hide => false,
-            DisplayScrollbar::Auto => list_height > menu_height,
-        };
+    fn step(&mut self, direction: Direction) -> Option<R> {
+        let index = self.state.selected;
+        if !self.selectable(index) {
+            return None;
+        }
 
-        let menu_display_area = if draw_scrollbar {
-            let scrollbar_area = content_area.resized_width(2, AnchorX::Right);
-            let thin_stroke = PrimitiveStyle::with_stroke(self.style.theme.text_color(), 1);
+        let value = self.items.step(index, direction)?;
+        self.emit(MenuEvent::ValueChanged { index });
+        self.store_setting(index);
+        self.apply_conditions();
+        Some(value)
+    }
 
-            let scale = |value| value * menu_height / list_height;
+    /// Writes the value of an item into the storage of the menu.
+    fn store_setting(&mut self, index: usize) {
+        if !self.items.store_setting_of(index, &mut self.storage) {
+            self.emit(MenuEvent::StoreFailed { index });
+        }
+    }
 
-            let scrollbar_height = scale(menu_height).max(1);
-            let mut scrollbar_display = display.cropped(&scrollbar_area);
+    /// Evaluates the conditions of the items after a value has changed.
+    ///
+    /// Items are arranged by the next [`Menu::update`] call.
+    fn apply_conditions(&mut self) {
+        self.needs_layout |= apply_conditions(&mut self.items);
+
+        if !self.selectable(self.state.selected) {
+            let next = Navigation::Next.calculate_selection(
+                self.state.selected,
+                self.items.count(),
+                |i| self.selectable(i),
+            );
+            self.select(next);
+        }
+    }
+
+    /// Returns the index of the menu item displayed at the given point.
+    ///
+    /// The position is calculated using the display area passed to the last [`Menu::update`]
+    /// call.
+    pub fn item_at(&self, point: Point) -> Option<usize> {
+        let display_area = self.display_area;
+        if !display_area.contains(point)
+            || self.footer_area(display_area).contains(point)
+            || self.help_line_area(display_area).contains(point)
+        {
+            return None;
+        }
+
+        if let MenuLayout::Carousel { page_dots, .. } = self.style.layout {
+            let offset = self.carousel_offset(self.content_area(self.display_area), page_dots);
+            return (0..self.items.count()).find(|&i| {
+                tabs::displays(&self.items, self.current_tab, i)
+                    && self.items.bounds_of(i).contains(point - offset)
+            });
+        }
+
+        let header_height = self.header_area(self.display_area).size.height as i32;
+
+        let selected_height = self.items.bounds_of(self.state.selected).size().height as i32;
+        let padding = self
+            .style
+            .indicator
+            .padding(selected_height, &self.state.indicator_state);
+
+        let y = point.y - self.display_area.top_left.y - header_height - padding.top
+            + self.state.list_offset;
+
+        if y < 0 {
+            return None;
+        }
+
+        let x = point.x - self.display_area.top_left.x - padding.left;
+
+        let count = self.items.count();
+        (0..count).find(|&i| {
+            if !tabs::displays(&self.items, self.current_tab, i) {
+                return false;
+            }
+
+            let bounds = self.items.bounds_of(i);
+            let top = bounds.top_left.y;
+            let left = bounds.top_left.x;
+
+            // List items span the whole width of the menu
+            let in_column = match self.style.layout {
+                MenuLayout::Grid { .. } => left <= x && x < left + bounds.size().width as i32,
+                MenuLayout::List | MenuLayout::Carousel { .. } => true,
+            };
+
+            in_column && top <= y && y < top + bounds.size().height as i32
+        })
+    }
+
+    /// Returns the part of `display_area` between the header and the footer, above the help line.
+    fn content_area(&self, display_area: Rectangle) -> Rectangle {
+        let header_height = self.header_area(display_area).size.height;
+        let footer_height = self.footer_area(display_area).size.height
+            + self.help_line_area(display_area).size.height;
+
+        Rectangle::new(
+            display_area.top_left + Point::new(0, header_height as i32),
+            Size::new(
+                display_area.size.width,
+                display_area
+                    .size
+                    .height
+                    .saturating_sub(header_height + footer_height),
+            ),
+        )
+    }
+
+    /// Returns the labels of the soft keys in the current state of the menu.
+    fn soft_keys(&self) -> &'static [&'static str] {
+        let Some(soft_keys) = self.style.footer else {
+            return &[];
+        };
+
+        let mode = if self.overlay.is_some() {
+            MenuMode::Message
+        } else if self.dialog.is_some() {
+            MenuMode::Confirming
+        } else if self.help.is_some() {
+            MenuMode::Help
+        } else if let InputState::InProgress(_) = self.state.last_input_state {
+            MenuMode::Editing
+        } else {
+            MenuMode::Navigating
+        };
+
+        let selected = self.state.selected;
+        soft_keys(&FooterContext {
+            mode,
+            selected,
+            selected_id: self.items.id_of(selected),
+            selected_title: self.items.title_of(selected),
+        })
+    }
+
+    /// Returns the part of `display_area` covered by the footer.
+    fn footer_area(&self, display_area: Rectangle) -> Rectangle {
+        let height = footer::height(self.soft_keys(), self.style.font);
+
+        display_area.resized_height(height.min(display_area.size.height), AnchorY::Bottom)
+    }
+
+    /// Returns the part of `display_area` above the footer, where help texts are displayed.
+    fn help_page_area(&self, display_area: Rectangle) -> Rectangle {
+        let footer_height = self.footer_area(display_area).size.height;
+
+        display_area.resized_height(display_area.size.height - footer_height, AnchorY::Top)
+    }
+
+    /// Returns the part of `display_area` covered by the help line, directly above the footer.
+    fn help_line_area(&self, display_area: Rectangle) -> Rectangle {
+        let height = if self.style.help_line {
+            help::line_height(self.style.font)
+        } else {
+            0
+        };
+        let area = self.help_page_area(display_area);
+
+        area.resized_height(height.min(area.size.height), AnchorY::Bottom)
+    }
+
+    /// Returns the offset that moves the items of a carousel from their arranged position into
+    /// `content_area`, with the selected item in the center.
+    fn carousel_offset(&self, content_area: Rectangle, page_dots: bool) -> Point {
+        let row_height = (0..self.items.count())
+            .map(|nth| self.items.bounds_of(nth).size.height)
+            .max()
+            .unwrap_or(0);
+        let row_area_height = if page_dots {
+            content_area
+                .size
+                .height
+                .saturating_sub(layout::PAGE_DOTS_HEIGHT)
+        } else {
+            content_area.size.height
+        };
+
+        // The indicator controller animates the left edge of the selected item
+        let selected_width = self.items.bounds_of(self.state.selected).size.width as i32;
+        let left = self.style.indicator.offset(&self.state.indicator_state);
+
+        Point::new(
+            content_area.center().x - left - selected_width / 2,
+            content_area.top_left.y + (row_area_height.saturating_sub(row_height) / 2) as i32,
+        )
+    }
+
+    /// Passes the state of the menu that the header displays to `f`.
+    fn with_header_context<U>(&self, f: impl FnOnce(&HeaderContext<'_>) -> U) -> U {
+        let list_top = if self.items.tab_count() == 0 {
+            0
+        } else {
+            tabs::extent(&self.items, self.current_tab).0
+        };
+
+        f(&HeaderContext::new(
+            self.title.as_ref(),
+            self.style.title_font,
+            self.state.selected,
+            self.items.count(),
+            self.state.list_offset - list_top,
+            &MenuTabs::new(&self.items, self.current_tab),
+        ))
+    }
+
+    /// Returns the part of `display_area` covered by the header.
+    fn header_area(&self, display_area: Rectangle) -> Rectangle {
+        let height = self.with_header_context(|context| {
+            self.style.header.height(context, display_area.size.width)
+        });
+
+        display_area.resized_height(height.min(display_area.size.height), AnchorY::Top)
+    }
+
+    pub fn state(&self) -> MenuState<IT::InputAdapter, P, S> {
+        self.state
+    }
+
+    /// Displays `text` over the menu for `ticks` updates, or until the next interaction.
+    ///
+    /// The text is copied, and cut off after [`MESSAGE_LENGTH`](overlay::MESSAGE_LENGTH) bytes.
+    /// Use [`write!`] on the returned overlay to append formatted values. While the message is
+    /// displayed, the menu doesn't react to input.
+    pub fn show_toast(&mut self, text: &str, ticks: u32) -> &mut Overlay {
+        self.overlay.insert(Overlay::toast(text, ticks))
+    }
+
+    /// Displays `text` over the menu until the next interaction.
+    ///
+    /// The text is copied, and cut off after [`MESSAGE_LENGTH`](overlay::MESSAGE_LENGTH) bytes.
+    /// Use [`write!`] on the returned overlay to append formatted values. While the message is
+    /// displayed, the menu doesn't react to input.
+    pub fn show_popup(&mut self, text: &str) -> &mut Overlay {
+        self.overlay.insert(Overlay::popup(text))
+    }
+
+    /// Returns the message displayed over the menu, if any.
+    pub fn overlay(&self) -> Option<&Overlay> {
+        self.overlay.as_ref()
+    }
+
+    /// Closes the message displayed over the menu.
+    pub fn dismiss_overlay(&mut self) {
+        self.overlay = None;
+    }
+
+    /// Displays the help text of the selected item in place of the menu, until the next
+    /// interaction other than navigation. Navigation scrolls the text.
+    ///
+    /// Returns `false` if the selected item has no help text.
+    pub fn show_help(&mut self) -> bool {
+        let index = self.state.selected;
+        let has_help = self.items.help_of(index).is_some();
+        if has_help {
+            self.help = Some(HelpPage::new(index));
+        }
+        has_help
+    }
+
+    /// Returns the index of the item whose help text is displayed, if any.
+    pub fn help_page(&self) -> Option<usize> {
+        self.help.as_ref().map(|page| page.index)
+    }
+
+    /// Closes the displayed help text.
+    pub fn close_help(&mut self) {
+        self.help = None;
+    }
+
+    /// Returns the confirmation the menu is waiting for, if a confirmation dialog is open.
+    pub fn pending_confirmation(&self) -> Option<Confirmation> {
+        self.dialog
+            .as_ref()
+            .and_then(|dialog| self.items.confirmation_of(dialog.index))
+    }
+
+    /// Closes the open confirmation dialog without activating the item.
+    pub fn cancel_confirmation(&mut self) {
+        self.dialog = None;
+    }
+
+    /// Returns the identifier of the selected item, if it has one.
+    pub fn selected_id(&self) -> Option<u16> {
+        self.items.id_of(self.state.selected)
+    }
+
+    /// Selects the item with the given identifier.
+    ///
+    /// Returns `false` and keeps the current selection if there is no selectable item with the
+    /// identifier.
+    pub fn select_by_id(&mut self, id: u16) -> bool {
+        match self.items.index_of(id) {
+            Some(index) if self.items.selectable(index) => {
+                self.select_tab(tabs::tab_of(&self.items, index));
+                self.select(index);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Writes the values of the items that are bound to a storage key into `storage`.
+    ///
+    /// Menus built with [`MenuBuilder::with_storage`] keep their own storage up to date, this
+    /// function is only needed to write the values into a different storage.
+    ///
+    /// Returns `false` if `storage` failed to store any of the values.
+    pub fn store_settings(&self, storage: &mut impl SettingsStorage) -> bool {
+        self.items.store_settings(storage)
+    }
+
+    /// Returns the storage of the menu.
+    pub fn storage(&self) -> &ST {
+        &self.storage
+    }
+
+    /// Returns the storage of the menu, e.g. to commit a
+    /// [`MemoryStorage`](crate::storage::MemoryStorage) to flash.
+    pub fn storage_mut(&mut self) -> &mut ST {
+        &mut self.storage
+    }
+}
+
+impl<T, IT, VG, R, P, S, C, H, E, ST> Menu<T, IT, VG, R, P, S, C, H, E, ST>
+where
+    T: AsRef<str>,
+    R: Copy,
+    IT: InputAdapterSource<R>,
+    VG: MenuItemCollection<R>,
+    C: Theme,
+    P: SelectionIndicatorController,
+    S: IndicatorStyle,
+    H: HeaderStyle,
+    E: FnMut(MenuEvent),
+    ST: SettingsStorage,
+{
+    pub fn selected_value(&self) -> R {
+        self.items.value_of(self.state.selected)
+    }
+
+    /// Returns the value of the `nth` item, without interacting with it.
+    pub fn value_of(&self, nth: usize) -> R {
+        self.items.value_of(nth)
+    }
+
+    /// Returns the value of the item with the given identifier, without interacting with it.
+    pub fn value_by_id(&self, id: u16) -> Option<R> {
+        self.items
+            .index_of(id)
+            .map(|index| self.items.value_of(index))
+    }
+}
+
+impl<T, IT, VG, R, C, P, S, H, E, ST> Menu<T, IT, VG, R, P, S, C, H, E, ST>
+where
+    T: AsRef<str>,
+    IT: InputAdapterSource<R>,
+    VG: ViewGroup + MenuItemCollection<R>,
+    P: SelectionIndicatorController,
+    S: IndicatorStyle,
+    C: Theme,
+    H: HeaderStyle,
+    E: FnMut(MenuEvent),
+    ST: SettingsStorage,
+{
+    fn top_offset(&self) -> i32 {
+        self.style.indicator.offset(&self.state.indicator_state) - self.state.list_offset
+    }
+
+    /// Returns the input state displayed on the selected item.
+    fn item_input_state(&self) -> InputState {
+        // Input progress belongs to the dialog while it is open.
+        if self.dialog.is_some() {
+            InputState::Idle
+        } else {
+            self.state.last_input_state
+        }
+    }
+
+    fn draw_list<D>(&self, content_area: Rectangle, display: &mut D) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = C::Color>,
+    {
+        let menu_height = content_area.size().height as i32;
+        let (list_top, list_height) = if self.items.tab_count() == 0 {
+            (0, self.items.bounds().size().height as i32)
+        } else {
+            tabs::extent(&self.items, self.current_tab)
+        };
+
+        let draw_scrollbar = match self.style.scrollbar {
+            DisplayScrollbar::Display => true,
+            DisplayScrollbar::Hide => false,
+            DisplayScrollbar::Auto => list_height > menu_height,
+        };
+
+        let menu_display_area = if draw_scrollbar {
+            let scrollbar_area = content_area.resized_width(2, AnchorX::Right);
+            let thin_stroke = PrimitiveStyle::with_stroke(self.style.theme.text_color(), 1);
+
+            let scale = |value| value * menu_height / list_height;
+
+            let scrollbar_height = scale(menu_height).max(1);
+            let mut scrollbar_display = display.cropped(&scrollbar_area);
+
+            // Rounding may push a short scrollbar below the display area on long lists.
+            let scrollbar_offset = scale(self.state.list_offset - list_top)
+                .min(menu_height - scrollbar_height - 1)
+                .max(0);
 
             // Start scrollbar from y=1, so we have a margin on top instead of bottom
             Line::new(Point::new(0, 1), Point::new(0, scrollbar_height))
                 .into_styled(thin_stroke)
-                .translate(Point::new(1, scale(self.state.list_offset)))
+                .translate(Point::new(1, scrollbar_offset))
                 .draw(&mut scrollbar_display)?;
 
             content_area.resized_width(
@@ -517,11 +1373,18 @@ where
                 .size()
                 .height as i32;
 
+        // Hide the items of the other tabs
+        let mut list_display = display.cropped(&menu_display_area);
+        let list_area = Rectangle::new(
+            Point::new(0, list_top - self.state.list_offset),
+            Size::new(menu_display_area.size.width, list_height.max(0) as u32),
+        );
+
         self.style.indicator.draw(
             selected_menuitem_height,
             self.top_offset(),
-            self.state.last_input_state,
-            display.cropped(&menu_display_area),
+            self.item_input_state(),
+            list_display.clipped(&list_area),
             &self.items,
             &self.style,
             &self.state,
@@ -529,4 +1392,1161 @@ where
 
         Ok(())
     }
+
+    fn draw_carousel<D>(
+        &self,
+        content_area: Rectangle,
+        page_dots: bool,
+        display: &mut D,
+    ) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = C::Color>,
+    {
+        self.style.indicator.draw_carousel(
+            self.carousel_offset(content_area, page_dots),
+            self.item_input_state(),
+            display.clipped(&content_area),
+            &self.items,
+            &self.style,
+            &self.state,
+        )?;
+
+        if page_dots {
+            let dots_area = content_area.resized_height(layout::PAGE_DOTS_HEIGHT, AnchorY::Bottom);
+            layout::draw_page_dots(
+                &self.items,
+                self.state.selected,
+                tabs::range(&self.items, self.current_tab),
+                dots_area,
+                &self.style.theme,
+                display,
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Advances animations and scrolls the selected item into view.
+    ///
+    /// If the menu has an idle timeout, this also counts the updates without input. Call
+    /// [`Menu::poll_idle`] afterwards to receive the value of an [`IdleAction::Return`] timeout.
+    pub fn update(&mut self, display: &impl Dimensions) {
+        self.display_area = display.bounding_box();
+
+        if self.needs_layout {
+            self.needs_layout = false;
+            layout::arrange(&mut self.items, self.style.layout);
+            self.state
+                .set_selected_item(self.state.selected, &self.items, &self.style);
+        }
+
+        self.style
+            .input_adapter
+            .adapter()
+            .update(&mut self.state.interaction_state);
+
+        // animations
+        self.style
+            .indicator
+            .update(self.state.last_input_state, &mut self.state.indicator_state);
+
+        // Ensure selection indicator is always visible by moving the menu list.
+        let top_distance = self.top_offset();
+
+        let list_offset_change = if let MenuLayout::Carousel { .. } = self.style.layout {
+            // Carousels don't scroll vertically
+            0
+        } else if top_distance > 0 {
+            let content_height = self.content_area(display.bounding_box()).size.height as i32;
+
+            let selected_height = MenuItemCollection::bounds_of(&self.items, self.state.selected)
+                .size()
+                .height as i32;
+            let indicator_height = self
+                .style
+                .indicator
+                .item_height(selected_height, &self.state.indicator_state);
+
+            // Indicator is below display top. We only have to
+            // move if indicator bottom is below display bottom.
+            (top_distance + indicator_height - content_height).max(0)
+        } else {
+            // We need to move up
+            top_distance
+        };
+
+        // Move menu list.
+        self.state.list_offset += list_offset_change;
+
+        self.tick_overlay();
+        if let Some(dialog) = self.dialog.as_mut() {
+            dialog.tick(self.state.last_input_state);
+        }
+        self.tick_idle_timeout();
+    }
+
+    fn tick_overlay(&mut self) {
+        if self.overlay.as_mut().is_some_and(Overlay::tick) {
+            self.overlay = None;
+        }
+    }
+
+    fn tick_idle_timeout(&mut self) {
+        let Some(IdleTimeout { ticks, action }) = &self.style.idle_timeout else {
+            return;
+        };
+
+        // Don't time out again before the next input, or while the user reads a message or answers
+        // a question.
+        if self.timed_out || self.overlay.is_some() || self.dialog.is_some() || self.help.is_some()
+        {
+            return;
+        }
+
+        self.idle_ticks = self.idle_ticks.saturating_add(1);
+        if self.idle_ticks < *ticks {
+            return;
+        }
+
+        let reset = matches!(action, IdleAction::Reset);
+        self.idle_ticks = 0;
+        self.timed_out = true;
+        self.emit(MenuEvent::IdleTimeout);
+
+        if reset {
+            let first = Navigation::Beginning.calculate_selection(
+                self.state.selected,
+                self.items.count(),
+                |i| self.selectable(i),
+            );
+            self.select(first);
+        } else {
+            self.idle_return = true;
+        }
+    }
+
+    /// Returns the value of the [`IdleAction::Return`] timeout, if the menu timed out since the
+    /// last call.
+    ///
+    /// The menu times out once, and only starts counting again after the next input.
+    pub fn poll_idle(&mut self) -> Option<R>
+    where
+        R: Clone,
+    {
+        if !core::mem::take(&mut self.idle_return) {
+            return None;
+        }
+
+        match &self.style.idle_timeout {
+            Some(IdleTimeout {
+                action: IdleAction::Return(value),
+                ..
+            }) => Some(value.clone()),
+            _ => None,
+        }
+    }
+
+    /// Hides the items whose title doesn't start with `filter`, ignoring ASCII case.
+    ///
+    /// If the selected item is hidden, the next visible item is selected. If no item matches, the
+    /// hidden item stays selected but can't be activated or changed. Pass an empty string to show
+    /// every item.
+    pub fn set_filter(&mut self, filter: &str) {
+        self.items.filter(filter);
+        layout::arrange(&mut self.items, self.style.layout);
+
+        self.reselect();
+    }
+
+    /// Shows or hides the `nth` item, and arranges the items.
+    ///
+    /// If the selected item is hidden, the next selectable item is selected. Returns whether the
+    /// item's visibility changed, which is never the case if the item can't be hidden.
+    pub fn set_visible(&mut self, nth: usize, visible: bool) -> bool {
+        let changed = self.items.set_visible(nth, visible);
+        if changed {
+            layout::arrange(&mut self.items, self.style.layout);
+            self.reselect();
+        }
+        changed
+    }
+
+    /// Enables or disables the `nth` item.
+    ///
+    /// If the selected item is disabled, the next selectable item is selected. Returns whether the
+    /// item changed, which is never the case if the item can't be disabled.
+    pub fn set_enabled(&mut self, nth: usize, enabled: bool) -> bool {
+        let changed = self.items.set_enabled(nth, enabled);
+        if changed {
+            self.reselect();
+        }
+        changed
+    }
+
+    /// Moves the selection off of an item that can no longer be selected, and moves the selection
+    /// indicator to the selected item.
+    fn reselect(&mut self) {
+        if !self.selectable(self.state.selected) {
+            let next = Navigation::Next.calculate_selection(
+                self.state.selected,
+                self.items.count(),
+                |i| self.selectable(i),
+            );
+            self.select(next);
+        } else {
+            // Item positions may have changed.
+            self.state
+                .set_selected_item(self.state.selected, &self.items, &self.style);
+        }
+    }
+
+    /// Changes the value of the `nth` item, e.g. to reflect a change made outside of the menu.
+    ///
+    /// The value must have the same type as the value of the item. Items that don't provide their
+    /// [value](crate::items::MenuListItem::any_value), e.g. [`VirtualItems`], are passed the value
+    /// in its [stored representation](crate::items::menu_item::SelectValue::stored_value).
+    /// Returns `false` if the item doesn't have such a value.
+    ///
+    /// [`VirtualItems`]: crate::collection::VirtualItems
+    pub fn set_value<V: SelectValue + 'static>(&mut self, nth: usize, value: V) -> bool {
+        let changed = if self.items.any_value_of(nth).is_some() {
+            self.items.set_any_value_of(nth, &value)
+        } else {
+            value
+                .stored_value()
+                .is_some_and(|stored| self.items.set_stored_value(nth, stored))
+        };
+        if changed {
+            apply_conditions(&mut self.items);
+            self.relayout(nth);
+        }
+        changed
+    }
+
+    /// Changes the value of the item with the given identifier. See [`Menu::set_value`].
+    pub fn set_value_by_id<V: SelectValue + 'static>(&mut self, id: u16, value: V) -> bool {
+        self.items
+            .index_of(id)
+            .is_some_and(|index| self.set_value(index, value))
+    }
+
+    /// Gives `modify` access to the items of the menu, e.g. to change their titles with
+    /// [`MenuItem::set_title`](crate::items::MenuItem::set_title).
+    ///
+    /// The items are measured and arranged again afterwards, and the conditions of the items are
+    /// evaluated. Items added with the [`MenuBuilder`] are nested in
+    /// [`Chain`] and [`Link`] objects, the last item being the outermost
+    /// one.
+    pub fn modify_items<U>(&mut self, modify: impl FnOnce(&mut VG) -> U) -> U {
+        let result = modify(&mut self.items);
+
+        let text_style = self.style.text_style();
+        for nth in 0..self.items.count() {
+            self.items.set_style_of(nth, &text_style);
+        }
+        apply_conditions(&mut self.items);
+        layout::arrange(&mut self.items, self.style.layout);
+        self.reselect();
+
+        result
+    }
+
+    /// Measures the `nth` item again and arranges the items around it.
+    fn relayout(&mut self, nth: usize) {
+        self.items.set_style_of(nth, &self.style.text_style());
+        layout::arrange(&mut self.items, self.style.layout);
+
+        self.reselect();
+    }
+
+    /// Feeds a recorded event to the menu.
+    ///
+    /// Inputs are passed to [`Menu::interact`] and their result is returned. Updates call
+    /// [`Menu::update`] the recorded number of times, each followed by [`Menu::poll_idle`], and
+    /// return the last value of an idle timeout.
+    pub fn replay(
+        &mut self,
+        event: Event<<IT::InputAdapter as InputAdapter>::Input>,
+        display: &impl Dimensions,
+    ) -> Option<R>
+    where
+        R: Clone,
+    {
+        match event {
+            Event::Input(input) => self.interact(input),
+            Event::Update(count) => {
+                let mut result = None;
+                for _ in 0..count {
+                    self.update(display);
+                    result = self.poll_idle().or(result);
+                }
+                result
+            }
+        }
+    }
+}
+
+impl<T, IT, VG, R, C, P, S, H, E, ST> Drawable for Menu<T, IT, VG, R, P, S, C, H, E, ST>
+where
+    T: AsRef<str>,
+    IT: InputAdapterSource<R>,
+    VG: ViewGroup + MenuItemCollection<R>,
+    P: SelectionIndicatorController,
+    S: IndicatorStyle,
+    C: Theme,
+    H: HeaderStyle,
+    E: FnMut(MenuEvent),
+    ST: SettingsStorage,
+{
+    type Color = C::Color;
+    type Output = ();
+
+    fn draw<D>(&self, display: &mut D) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = C::Color>,
+    {
+        let display_area = display.bounding_box();
+
+        let header_area = self.header_area(display_area);
+        if !header_area.is_zero_sized() {
+            self.with_header_context(|context| {
+                self.style.header.draw(
+                    context,
+                    header_area,
+                    &self.style.theme,
+                    &mut display.clipped(&header_area),
+                )
+            })?;
+        }
+        let content_area = self.content_area(display_area);
+
+        if let MenuLayout::Carousel { page_dots, .. } = self.style.layout {
+            self.draw_carousel(content_area, page_dots, display)?;
+        } else {
+            self.draw_list(content_area, display)?;
+        }
+
+        let footer_area = self.footer_area(display_area);
+        if !footer_area.is_zero_sized() {
+            footer::draw(
+                self.soft_keys(),
+                footer_area,
+                self.style.font,
+                &self.style.theme,
+                &mut display.clipped(&footer_area),
+            )?;
+        }
+
+        let help_line_area = self.help_line_area(display_area);
+        if !help_line_area.is_zero_sized() {
+            help::draw_line(
+                self.items.help_of(self.state.selected).unwrap_or(""),
+                help_line_area,
+                self.style.font,
+                &self.style.theme,
+                &mut display.clipped(&help_line_area),
+            )?;
+        }
+
+        if let Some(page) = self.help.as_ref() {
+            page.draw(
+                self.items.title_of(page.index),
+                self.items.help_of(page.index).unwrap_or(""),
+                self.help_page_area(display_area),
+                self.style.font,
+                &self.style.theme,
+                display,
+            )?;
+        }
+
+        if let Some(dialog) = self.dialog.as_ref() {
+            if let Some(confirmation) = self.items.confirmation_of(dialog.index) {
+                dialog.draw(&confirmation, &self.style.theme, self.style.font, display)?;
+            }
+        }
+
+        if let Some(overlay) = self.overlay.as_ref() {
+            overlay.draw(&self.style.theme, self.style.font, display)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use embedded_graphics::{
+        mock_display::MockDisplay, pixelcolor::BinaryColor, prelude::Point, Drawable,
+    };
+
+    use std::vec::Vec;
+
+    use crate::{
+        collection::{ItemProvider, MenuItemCollection},
+        event::MenuEvent,
+        interaction::{
+            combined::{Combined, Either},
+            programmed::Programmed,
+            single_touch::SingleTouch,
+            Action, Interaction, Navigation,
+        },
+        items::{menu_item::SelectValue, starts_with_ignore_case, Confirm, MenuItem},
+        storage::{MemoryStorage, SettingsStorage},
+        IdleAction, Menu, MenuStyle, SavedState,
+    };
+
+    #[test]
+    fn click_selects_then_activates() {
+        let mut menu = Menu::build("Title")
+            .add_item("First", false, |v| (0, v))
+            .add_section_title("Section")
+            .add_item("Second", false, |v| (2, v))
+            .build();
+
+        let display = MockDisplay::<BinaryColor>::new();
+        menu.update(&display);
+
+        // Header is 11px tall, items are 9px tall
+        assert_eq!(menu.item_at(Point::new(5, 5)), None);
+        assert_eq!(menu.item_at(Point::new(5, 11)), Some(0));
+        assert_eq!(menu.item_at(Point::new(5, 19)), Some(0));
+        assert_eq!(menu.item_at(Point::new(5, 20)), Some(1));
+        assert_eq!(menu.item_at(Point::new(5, 29)), Some(2));
+        assert_eq!(menu.item_at(Point::new(5, 40)), None);
+
+        // Section titles can't be selected
+        assert_eq!(menu.interact(Interaction::Click(Point::new(5, 20))), None);
+        assert_eq!(menu.selected_value(), (0, false));
+
+        assert_eq!(menu.interact(Interaction::Click(Point::new(5, 29))), None);
+        assert_eq!(menu.selected_value(), (2, false));
+        assert_eq!(
+            menu.interact(Interaction::Click(Point::new(5, 29))),
+            Some((2, true))
+        );
+
+        assert_eq!(
+            menu.interact(Interaction::Navigation(Navigation::Right)),
+            Some((2, false))
+        );
+        assert_eq!(
+            menu.interact(Interaction::Action(Action::Select)),
+            Some((2, true))
+        );
+    }
+
+    #[test]
+    fn events_are_reported() {
+        let mut events = Vec::new();
+        let input = Combined {
+            left: Programmed,
+            right: SingleTouch::new(0, 0, 5),
+        };
+        let mut menu = Menu::with_style("Title", MenuStyle::default().with_input_adapter(input))
+            .add_item("First", false, |v| v)
+            .add_section_title("Section")
+            .add_item("Second", false, |v| v)
+            .with_event_handler(|event| events.push(event))
+            .build();
+
+        let mut navigate = |navigation| {
+            menu.interact(Either::Left(Interaction::Navigation(navigation)));
+        };
+        navigate(Navigation::Next);
+        navigate(Navigation::End);
+        navigate(Navigation::Right);
+
+        // Long press
+        for pressed in [false, true, true, true, true, true, false] {
+            menu.interact(Either::Right(pressed));
+        }
+        menu.interact(Either::Left(Interaction::Action(Action::Select)));
+        menu.interact(Either::Left(Interaction::Action(Action::Return(false))));
+
+        assert_eq!(
+            events,
+            [
+                MenuEvent::SelectionChanged { old: 0, new: 2 },
+                MenuEvent::ValueChanged { index: 2 },
+                MenuEvent::ValueChanged { index: 2 },
+                MenuEvent::EditCommitted { index: 2 },
+                MenuEvent::ValueChanged { index: 2 },
+                MenuEvent::EditCommitted { index: 2 },
+                MenuEvent::Exited,
+            ]
+        );
+    }
+
+    #[test]
+    fn changes_of_values_without_stored_representation_are_reported() {
+        #[derive(Clone, Copy, PartialEq, Debug)]
+        struct Cycle(u8);
+
+        impl SelectValue for Cycle {
+            fn next(&mut self) {
+                self.0 = (self.0 + 1) % 2;
+            }
+
+            fn marker(&self) -> &str {
+                ""
+            }
+        }
+
+        let mut events = Vec::new();
+        let mut menu = Menu::build("Title")
+            .add_menu_item(MenuItem::new("Cycle", Cycle(0)).with_value_converter(|v| v.0))
+            .add_menu_item(MenuItem::new("Action", ()).with_value_converter(|_| 2))
+            .add_menu_item(Confirm::new(
+                MenuItem::new("Reset", ()).with_value_converter(|_| 3),
+                "Reset?",
+            ))
+            .with_event_handler(|event| events.push(event))
+            .build();
+
+        assert_eq!(menu.interact(Interaction::Action(Action::Select)), Some(1));
+        menu.interact(Interaction::Navigation(Navigation::Next));
+        menu.interact(Interaction::Action(Action::Select));
+        menu.interact(Interaction::Navigation(Navigation::Next));
+        menu.interact(Interaction::Action(Action::Select));
+
+        assert_eq!(
+            events,
+            [
+                MenuEvent::ValueChanged { index: 0 },
+                MenuEvent::EditCommitted { index: 0 },
+                MenuEvent::SelectionChanged { old: 0, new: 1 },
+                MenuEvent::EditCommitted { index: 1 },
+                MenuEvent::SelectionChanged { old: 1, new: 2 },
+                MenuEvent::ConfirmationRequested { index: 2 },
+            ]
+        );
+    }
+
+    #[test]
+    fn saved_state_roundtrip() {
+        for selected_id in [None, Some(300)] {
+            let saved = SavedState {
+                selected: 70_000,
+                selected_id,
+                list_offset: 27,
+            };
+
+            let bytes = saved.to_bytes();
+            assert_eq!(SavedState::from_bytes(&bytes), Some(saved));
+            assert_eq!(SavedState::from_bytes(&bytes[..11]), None);
+        }
+
+        let saved = SavedState {
+            selected: usize::MAX,
+            ..SavedState::default()
+        };
+        assert_eq!(SavedState::from_bytes(&saved.to_bytes()), Some(saved));
+
+        assert_eq!(SavedState::from_bytes(&[0, 3, 0, 27, 0, 0, 0]), None);
+        assert_eq!(
+            SavedState::from_bytes(&[1, 3, 0, 27, 0, 0, 0]),
+            Some(SavedState {
+                selected: 3,
+                selected_id: None,
+                list_offset: 27,
+            })
+        );
+        assert_eq!(
+            SavedState::from_bytes(&[2, 3, 0, 27, 0, 0, 0, 1, 44, 1]),
+            Some(SavedState {
+                selected: 3,
+                selected_id: Some(300),
+                list_offset: 27,
+            })
+        );
+    }
+
+    #[test]
+    fn saved_state_is_clamped_to_fewer_items() {
+        let menu = Menu::build("Title")
+            .add_item("First", false, |v| v)
+            .add_item("Second", false, |v| v)
+            .build_with_saved_state(SavedState {
+                selected: 5,
+                selected_id: None,
+                list_offset: 100,
+            });
+
+        assert_eq!(
+            menu.state().saved(),
+            SavedState {
+                selected: 1,
+                selected_id: None,
+                list_offset: 9,
+            }
+        );
+    }
+
+    #[test]
+    fn selection_is_restored_by_id() {
+        let mut menu = Menu::build("Title")
+            .add_menu_item(
+                MenuItem::new("First", ())
+                    .with_value_converter(|_| 0)
+                    .with_id(10),
+            )
+            .add_menu_item(
+                MenuItem::new("Second", ())
+                    .with_value_converter(|_| 1)
+                    .with_id(20),
+            )
+            .add_menu_item(
+                MenuItem::new("Section", ())
+                    .with_value_converter(|_| 2)
+                    .with_id(30)
+                    .selectable::<false>(),
+            )
+            .build();
+
+        assert!(menu.select_by_id(20));
+        assert!(!menu.select_by_id(30));
+        assert!(!menu.select_by_id(40));
+        assert_eq!(menu.selected_id(), Some(20));
+
+        let state = menu.state();
+
+        let build = || {
+            Menu::build("Title")
+                .add_item("Inserted", (), |_| 3)
+                .add_menu_item(
+                    MenuItem::new("First", ())
+                        .with_value_converter(|_| 0)
+                        .with_id(10),
+                )
+                .add_menu_item(
+                    MenuItem::new("Second", ())
+                        .with_value_converter(|_| 1)
+                        .with_id(20),
+                )
+        };
+
+        assert_eq!(build().build_with_state(state).selected_value(), 0);
+
+        let menu = build().build_with_state_by_id(state);
+        assert_eq!(menu.selected_value(), 1);
+        assert_eq!(menu.selected_id(), Some(20));
+
+        let menu = build().build_with_saved_state(state.saved());
+        assert_eq!(menu.selected_value(), 1);
+    }
+
+    #[test]
+    fn jump_to_letter_and_filter() {
+        let mut slice = [
+            MenuItem::new("Cranberry", ()).with_value_converter(|_| 4),
+            MenuItem::new("Date", ()).with_value_converter(|_| 5),
+        ];
+        let mut menu = Menu::build("Title")
+            .add_item("Apple", (), |_| 0)
+            .add_item("Banana", (), |_| 1)
+            .add_item("Blueberry", (), |_| 2)
+            .add_item("Cherry", (), |_| 3)
+            .add_menu_items(&mut slice)
+            .build();
+
+        let display = MockDisplay::<BinaryColor>::new();
+        let mut jump = |letter| {
+            menu.interact(Interaction::Navigation(Navigation::JumpToLetter(letter)));
+            menu.selected_value()
+        };
+        assert_eq!(jump('b'), 1);
+        assert_eq!(jump('B'), 2);
+        assert_eq!(jump('b'), 1);
+        assert_eq!(jump('z'), 1);
+        assert_eq!(jump('c'), 3);
+        assert_eq!(jump('c'), 4);
+        assert_eq!(jump('b'), 1);
+
+        menu.set_filter("c");
+        menu.update(&display);
+        assert_eq!(menu.selected_value(), 3);
+
+        // Header is 11px tall, items are 9px tall
+        assert_eq!(menu.item_at(Point::new(5, 12)), Some(3));
+        assert_eq!(menu.item_at(Point::new(5, 21)), Some(4));
+        assert_eq!(menu.item_at(Point::new(5, 30)), None);
+
+        menu.interact(Interaction::Navigation(Navigation::Next));
+        assert_eq!(menu.selected_value(), 4);
+        menu.interact(Interaction::Navigation(Navigation::Next));
+        assert_eq!(menu.selected_value(), 3);
+
+        menu.set_filter("");
+        menu.update(&display);
+        assert_eq!(menu.selected_value(), 3);
+        assert_eq!(menu.item_at(Point::new(5, 12)), Some(0));
+        assert_eq!(menu.item_at(Point::new(5, 57)), Some(5));
+    }
+
+    #[test]
+    fn filter_without_matches_disables_interaction() {
+        let mut menu = Menu::build("Title")
+            .add_menu_item(MenuItem::new("Apple", false).with_value_converter(|v| v as u8))
+            .add_item("Banana", (), |_| 2)
+            .build();
+
+        menu.set_filter("x");
+        assert_eq!(menu.interact(Interaction::Action(Action::Select)), None);
+        assert_eq!(
+            menu.interact(Interaction::Navigation(Navigation::Right)),
+            None
+        );
+        assert_eq!(menu.selected_value(), 0);
+
+        menu.set_filter("a");
+        assert_eq!(menu.interact(Interaction::Action(Action::Select)), Some(1));
+    }
+
+    struct Numbers;
+
+    // `is_multiple_of` is not available on our MSRV.
+    #[allow(clippy::manual_is_multiple_of)]
+    impl ItemProvider<usize> for Numbers {
+        fn count(&self) -> usize {
+            1000
+        }
+
+        fn title(&self, nth: usize) -> &str {
+            if nth % 10 == 0 {
+                "Section"
+            } else {
+                "Number"
+            }
+        }
+
+        fn value_of(&self, nth: usize) -> usize {
+            nth
+        }
+
+        fn interact(&mut self, nth: usize) -> usize {
+            nth
+        }
+
+        fn selectable(&self, nth: usize) -> bool {
+            nth % 10 != 0
+        }
+
+        fn help(&self, nth: usize) -> Option<&str> {
+            (nth % 10 == 5).then_some("Divisible by five")
+        }
+
+        fn id(&self, nth: usize) -> Option<u16> {
+            Some(nth as u16)
+        }
+    }
+
+    #[test]
+    fn virtual_items() {
+        let mut menu = Menu::build("Title")
+            .add_item("First", (), |_| 10_000)
+            .add_virtual_items(Numbers)
+            .build();
+
+        let mut display = MockDisplay::<BinaryColor>::new();
+        display.set_allow_overdraw(true);
+
+        menu.interact(Interaction::Navigation(Navigation::Next));
+        assert_eq!(menu.selected_value(), 1);
+
+        menu.interact(Interaction::Navigation(Navigation::End));
+        menu.update(&display);
+        menu.draw(&mut display).unwrap();
+        assert_eq!(menu.selected_value(), 999);
+
+        assert!(menu.select_by_id(501));
+        assert_eq!(menu.selected_value(), 501);
+        assert!(!menu.show_help());
+
+        assert!(menu.select_by_id(505));
+        assert!(menu.show_help());
+        assert_eq!(menu.help_page(), Some(506));
+        menu.interact(Interaction::Action(Action::Select));
+
+        // Header is 11px tall, items are 9px tall
+        menu.interact(Interaction::Navigation(Navigation::Beginning));
+        menu.update(&display);
+        assert_eq!(menu.item_at(Point::new(5, 12)), Some(0));
+        assert_eq!(menu.item_at(Point::new(5, 21)), Some(1));
+        assert_eq!(menu.item_at(Point::new(5, 28)), Some(1));
+        assert_eq!(menu.item_at(Point::new(5, 29)), Some(2));
+    }
+
+    /// Provides the words that start with the filter text.
+    struct Words {
+        first: usize,
+        count: usize,
+    }
+
+    impl Words {
+        const WORDS: [&'static str; 4] = ["Apple", "Banana", "Blueberry", "Cherry"];
+    }
+
+    impl ItemProvider<&'static str> for Words {
+        fn count(&self) -> usize {
+            self.count
+        }
+
+        fn title(&self, nth: usize) -> &str {
+            Self::WORDS[self.first + nth]
+        }
+
+        fn value_of(&self, nth: usize) -> &'static str {
+            Self::WORDS[self.first + nth]
+        }
+
+        fn interact(&mut self, nth: usize) -> &'static str {
+            self.value_of(nth)
+        }
+
+        fn filter(&mut self, filter: &str) {
+            let matches = |word: &&&str| starts_with_ignore_case(word, filter);
+            self.first = Self::WORDS
+                .iter()
+                .position(|word| matches(&word))
+                .unwrap_or(0);
+            self.count = Self::WORDS.iter().filter(matches).count();
+        }
+    }
+
+    #[test]
+    fn virtual_items_can_be_filtered() {
+        let mut menu = Menu::build("Title")
+            .add_virtual_items(Words { first: 0, count: 4 })
+            .build();
+
+        let mut display = MockDisplay::<BinaryColor>::new();
+        display.set_allow_overdraw(true);
+
+        menu.set_filter("b");
+        menu.interact(Interaction::Navigation(Navigation::Next));
+        assert_eq!(menu.selected_value(), "Blueberry");
+
+        // Nothing matches, so the menu has no items at all.
+        menu.set_filter("x");
+        menu.interact(Interaction::Navigation(Navigation::Next));
+        assert_eq!(menu.interact(Interaction::Action(Action::Select)), None);
+        menu.update(&display);
+        menu.draw(&mut display).unwrap();
+
+        menu.set_filter("");
+        menu.interact(Interaction::Navigation(Navigation::End));
+        assert_eq!(menu.selected_value(), "Cherry");
+    }
+
+    /// Provides switches whose values are stored under their own keys.
+    struct Switches([bool; 2]);
+
+    impl ItemProvider<bool> for Switches {
+        fn count(&self) -> usize {
+            self.0.len()
+        }
+
+        fn title(&self, _nth: usize) -> &str {
+            "Switch"
+        }
+
+        fn value_of(&self, nth: usize) -> bool {
+            self.0[nth]
+        }
+
+        fn interact(&mut self, nth: usize) -> bool {
+            self.0[nth] = !self.0[nth];
+            self.0[nth]
+        }
+
+        fn storage_key(&self, nth: usize) -> Option<&'static str> {
+            ["first", "second"].get(nth).copied()
+        }
+
+        fn stored_value(&self, nth: usize) -> Option<u32> {
+            Some(self.0[nth] as u32)
+        }
+
+        fn set_stored_value(&mut self, nth: usize, value: u32) -> bool {
+            self.0[nth] = value != 0;
+            true
+        }
+    }
+
+    #[test]
+    fn virtual_items_can_be_stored() {
+        let mut storage = MemoryStorage::<2>::new();
+        storage.set("second", 1);
+
+        let mut menu = Menu::build("Title")
+            .add_virtual_items(Switches([false; 2]))
+            .with_storage(&mut storage)
+            .build();
+
+        menu.interact(Interaction::Navigation(Navigation::Next));
+        assert!(menu.selected_value());
+
+        menu.interact(Interaction::Navigation(Navigation::Previous));
+        menu.interact(Interaction::Action(Action::Select));
+        assert_eq!(menu.storage().get("first"), Some(1));
+    }
+
+    #[test]
+    fn values_and_titles_can_be_changed() {
+        let mut menu = Menu::build("Title")
+            .add_item("Flag", false, |v| v as u8)
+            .add_menu_item(
+                MenuItem::new("Other", false)
+                    .with_value_converter(|v| v as u8 + 2)
+                    .with_id(7),
+            )
+            .add_item("Action", ">", |_| 4)
+            .build();
+
+        assert!(menu.set_value(0, true));
+        assert_eq!(menu.value_of(0), 1);
+
+        assert_eq!(menu.value_by_id(7), Some(2));
+        assert!(menu.set_value_by_id(7, true));
+        assert_eq!(menu.value_by_id(7), Some(3));
+        assert_eq!(menu.value_by_id(8), None);
+        assert!(!menu.set_value_by_id(8, true));
+
+        // Values are set by their type, also without a stored representation.
+        assert!(menu.set_value(2, "<"));
+        assert!(!menu.set_value(2, true));
+        assert!(!menu.set_value(0, ()));
+        assert_eq!(
+            menu.items.any_value_of(2).and_then(|v| v.downcast_ref()),
+            Some(&"<")
+        );
+
+        menu.modify_items(|items| items.parent.object.set_title("Renamed"));
+        assert_eq!(menu.items.title_of(1), "Renamed");
+
+        menu.set_filter("r");
+        assert_eq!(menu.selected_value(), 3);
+
+        // Titles can be borrowed from the application.
+        let title = std::format!("Count: {}", 5);
+        let mut menu = Menu::build("Title")
+            .add_menu_item(MenuItem::new("Count", ()).with_value_converter(|_| 0))
+            .build();
+
+        menu.modify_items(|items| items.object.set_title(&title));
+        assert_eq!(menu.items.title_of(0), "Count: 5");
+    }
+
+    #[test]
+    fn disabled_and_hidden_items_are_skipped() {
+        let mut menu = Menu::build("Title")
+            .add_item("First", (), |_| 0)
+            .add_menu_item(
+                MenuItem::new("Disabled", ())
+                    .with_value_converter(|_| 1)
+                    .with_enabled(false),
+            )
+            .add_menu_item(
+                MenuItem::new("Hidden", ())
+                    .with_value_converter(|_| 2)
+                    .with_visible(false),
+            )
+            .add_item("Last", (), |_| 3)
+            .build();
+
+        let display = MockDisplay::<BinaryColor>::new();
+        menu.update(&display);
+
+        // Header is 11px tall, items are 9px tall
+        assert_eq!(menu.item_at(Point::new(5, 21)), Some(1));
+        assert_eq!(menu.item_at(Point::new(5, 30)), Some(3));
+
+        menu.interact(Interaction::Navigation(Navigation::Next));
+        assert_eq!(menu.selected_value(), 3);
+
+        assert!(menu.set_visible(2, true));
+        assert!(!menu.set_visible(2, true));
+        assert_eq!(menu.item_at(Point::new(5, 30)), Some(2));
+        assert_eq!(menu.item_at(Point::new(5, 39)), Some(3));
+
+        assert!(menu.set_enabled(3, false));
+        assert!(!menu.set_enabled(3, false));
+        assert_eq!(menu.selected_value(), 0);
+
+        assert!(menu.set_enabled(1, true));
+        menu.interact(Interaction::Navigation(Navigation::Next));
+        assert_eq!(menu.selected_value(), 1);
+    }
+
+    #[test]
+    fn conditions_are_evaluated_after_interaction() {
+        #[derive(Clone, Copy, PartialEq, Debug)]
+        enum Setting {
+            Dhcp(bool),
+            Address,
+            Apply,
+        }
+
+        let mut menu = Menu::build("Network")
+            .add_menu_item(
+                MenuItem::new("DHCP", true)
+                    .with_value_converter(Setting::Dhcp)
+                    .with_id(1),
+            )
+            .add_menu_item(
+                MenuItem::new("Address", ())
+                    .with_value_converter(|_| Setting::Address)
+                    .visible_if(|values| values.has_value_by_id(1, false)),
+            )
+            .add_menu_item(
+                // Conditions are kept when the value converter is set.
+                MenuItem::new("Apply", ())
+                    .enabled_if(|values| values.has_value(0, false))
+                    .with_value_converter(|_| Setting::Apply),
+            )
+            .build();
+
+        let display = MockDisplay::<BinaryColor>::new();
+        menu.update(&display);
+
+        // Only DHCP is selectable
+        menu.interact(Interaction::Navigation(Navigation::Next));
+        assert_eq!(menu.selected_value(), Setting::Dhcp(true));
+        assert_eq!(menu.item_at(Point::new(5, 21)), Some(2));
+
+        menu.interact(Interaction::Action(Action::Select));
+        menu.update(&display);
+        assert_eq!(menu.item_at(Point::new(5, 21)), Some(1));
+        assert_eq!(menu.item_at(Point::new(5, 30)), Some(2));
+
+        menu.interact(Interaction::Navigation(Navigation::Next));
+        assert_eq!(menu.selected_value(), Setting::Address);
+        menu.interact(Interaction::Navigation(Navigation::Next));
+        assert_eq!(menu.selected_value(), Setting::Apply);
+
+        // Turning DHCP back on hides the address and disables the selected item
+        assert!(menu.set_value(0, true));
+        assert_eq!(menu.selected_value(), Setting::Dhcp(true));
+        assert_eq!(menu.item_at(Point::new(5, 21)), Some(2));
+    }
+
+    #[test]
+    fn conditions_compare_values_without_stored_representation() {
+        #[derive(Clone, Copy, PartialEq, Debug)]
+        enum Mode {
+            Auto,
+            Manual,
+        }
+
+        impl SelectValue for Mode {
+            fn next(&mut self) {
+                *self = match self {
+                    Mode::Auto => Mode::Manual,
+                    Mode::Manual => Mode::Auto,
+                };
+            }
+
+            fn marker(&self) -> &str {
+                match self {
+                    Mode::Auto => "Auto",
+                    Mode::Manual => "Manual",
+                }
+            }
+        }
+
+        let mut menu = Menu::build("Title")
+            .add_menu_item(MenuItem::new("Mode", Mode::Auto).with_value_converter(|_| 0))
+            .add_menu_item(
+                MenuItem::new("Speed", ())
+                    .with_value_converter(|_| 1)
+                    .visible_if(|values| values.has_value(0, Mode::Manual)),
+            )
+            .add_menu_item(
+                MenuItem::new("Limit", ())
+                    .with_value_converter(|_| 2)
+                    .enabled_if(|values| values.value_of::<Mode>(0) == Some(Mode::Manual)),
+            )
+            .build();
+
+        let display = MockDisplay::<BinaryColor>::new();
+        menu.update(&display);
+        assert_eq!(menu.item_at(Point::new(5, 21)), Some(2));
+
+        menu.interact(Interaction::Navigation(Navigation::Next));
+        assert_eq!(menu.selected_value(), 0);
+
+        menu.interact(Interaction::Action(Action::Select));
+        menu.update(&display);
+        assert_eq!(menu.item_at(Point::new(5, 21)), Some(1));
+        menu.interact(Interaction::Navigation(Navigation::Next));
+        menu.interact(Interaction::Navigation(Navigation::Next));
+        assert_eq!(menu.selected_value(), 2);
+    }
+
+    #[test]
+    fn idle_timeout() {
+        let mut display = MockDisplay::<BinaryColor>::new();
+        display.set_allow_overdraw(true);
+
+        let mut events = Vec::new();
+        let mut menu = Menu::with_style(
+            "Title",
+            MenuStyle::default().with_idle_timeout(3, IdleAction::Return(99)),
+        )
+        .add_item("First", (), |_| 0)
+        .add_item("Second", (), |_| 1)
+        .with_event_handler(|event| events.push(event))
+        .build();
+
+        menu.update(&display);
+        menu.update(&display);
+        assert_eq!(menu.poll_idle(), None);
+        menu.interact(Interaction::Navigation(Navigation::Next));
+        menu.update(&display);
+        menu.update(&display);
+        assert_eq!(menu.poll_idle(), None);
+        menu.update(&display);
+        assert_eq!(menu.poll_idle(), Some(99));
+        assert_eq!(menu.poll_idle(), None);
+
+        // The menu doesn't time out again without input
+        for _ in 0..10 {
+            menu.update(&display);
+        }
+        assert_eq!(menu.poll_idle(), None);
+
+        // The timeout is paused while an overlay is displayed
+        menu.show_popup("Popup");
+        for _ in 0..5 {
+            menu.update(&display);
+        }
+        assert_eq!(menu.poll_idle(), None);
+        menu.interact(Interaction::Action(Action::Select));
+        for _ in 0..3 {
+            menu.update(&display);
+        }
+        assert_eq!(menu.poll_idle(), Some(99));
+
+        let timeouts = events
+            .iter()
+            .filter(|&&event| event == MenuEvent::IdleTimeout)
+            .count();
+        assert_eq!(timeouts, 2);
+
+        let mut menu = Menu::with_style(
+            "Title",
+            MenuStyle::default().with_idle_timeout(2, IdleAction::Reset),
+        )
+        .add_section_title("Section")
+        .add_item("First", (), |_| 0)
+        .add_item("Second", (), |_| 1)
+        .build();
+
+        menu.interact(Interaction::Navigation(Navigation::Next));
+        assert_eq!(menu.selected_value(), 1);
+        menu.update(&display);
+        menu.update(&display);
+        assert_eq!(menu.selected_value(), 0);
+        assert_eq!(menu.poll_idle(), None);
+    }
 }
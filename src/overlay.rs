@@ -0,0 +1,371 @@
+//! Messages displayed on top of a menu.
+//!
+//! See [`Menu::show_toast`](crate::Menu::show_toast),
+//! [`Menu::show_popup`](crate::Menu::show_popup) and [`Confirm`](crate::items::Confirm).
+
+use embedded_graphics::{
+    geometry::AnchorPoint,
+    mono_font::{MonoFont, MonoTextStyle},
+    prelude::{DrawTarget, DrawTargetExt, Point, Primitive, Size, Transform},
+    primitives::{PrimitiveStyle, PrimitiveStyleBuilder, Rectangle},
+    text::{renderer::TextRenderer, Alignment, Baseline, Text, TextStyleBuilder},
+    Drawable,
+};
+use embedded_text::{
+    alignment::HorizontalAlignment,
+    style::{HeightMode, TextBoxStyleBuilder},
+    TextBox,
+};
+
+use core::fmt::{self, Write};
+
+use crate::{interaction::InputState, items::Confirmation, text::TextBuffer, theme::Theme};
+
+/// Space between the display edges and the frame.
+const MARGIN: u32 = 4;
+/// Space between the frame and the text.
+const PADDING: u32 = 2;
+/// The longest overlay text, in bytes. Longer text is cut off.
+pub const MESSAGE_LENGTH: usize = 64;
+
+/// A framed message that is drawn over the menu.
+///
+/// More text can be appended with [`write!`], e.g. to display a value.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Overlay {
+    text: TextBuffer<MESSAGE_LENGTH>,
+    /// The number of updates until the overlay is closed, or `None` if it is only closed by
+    /// input.
+    remaining_ticks: Option<u32>,
+}
+
+impl Overlay {
+    /// An overlay that is closed after `ticks` updates, or on input.
+    pub fn toast(text: &str, ticks: u32) -> Self {
+        Self::new(text, Some(ticks))
+    }
+
+    /// An overlay that is only closed on input.
+    pub fn popup(text: &str) -> Self {
+        Self::new(text, None)
+    }
+
+    fn new(text: &str, remaining_ticks: Option<u32>) -> Self {
+        let mut overlay = Self {
+            text: TextBuffer::new(),
+            remaining_ticks,
+        };
+        // Text that doesn't fit is cut off
+        _ = overlay.write_str(text);
+        overlay
+    }
+
+    /// Returns the displayed text.
+    pub fn text(&self) -> &str {
+        self.text.as_str()
+    }
+
+    /// Counts down the remaining time. Returns `true` when the overlay should be closed.
+    pub(crate) fn tick(&mut self) -> bool {
+        match self.remaining_ticks.as_mut() {
+            Some(remaining) => {
+                *remaining = remaining.saturating_sub(1);
+                *remaining == 0
+            }
+            None => false,
+        }
+    }
+
+    pub(crate) fn draw<C, D>(
+        &self,
+        theme: &C,
+        font: &'static MonoFont<'static>,
+        display: &mut D,
+    ) -> Result<(), D::Error>
+    where
+        C: Theme,
+        D: DrawTarget<Color = C::Color>,
+    {
+        draw_framed(self.text(), Size::zero(), theme, font, display).map(|_| ())
+    }
+}
+
+impl Write for Overlay {
+    /// Appends `s` to the displayed text. Fails if the text would be longer than
+    /// [`MESSAGE_LENGTH`], keeping the characters that fit.
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.text.write_str(s)
+    }
+}
+
+/// The confirmation dialog of an item wrapped in [`Confirm`](crate::items::Confirm).
+///
+/// The question is requested from the item when it is needed, so the dialog only stores what the
+/// user did.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct Dialog {
+    /// The index of the item that is activated when confirmed.
+    pub index: usize,
+    /// Whether the confirming answer is selected. Dialogs open with the cancelling answer
+    /// selected.
+    pub confirm_selected: bool,
+    /// The number of updates the select input has been held for.
+    pub held: u32,
+    /// Set when a selection was reported since the last update.
+    pub selected: bool,
+}
+
+impl Dialog {
+    pub const fn new(index: usize) -> Self {
+        Self {
+            index,
+            confirm_selected: false,
+            held: 0,
+            selected: false,
+        }
+    }
+
+    /// Returns whether the user confirmed, taking `confirmation`'s hold requirement into account.
+    pub fn is_confirmed(&self, confirmation: &Confirmation) -> bool {
+        self.confirm_selected
+            && !matches!(confirmation.hold_ticks(), Some(ticks) if self.held < ticks)
+    }
+
+    /// Counts how long the select input has been held. The input is held while the input adapter
+    /// reports progress, or a selection on every update.
+    pub fn tick(&mut self, input_state: InputState) {
+        let selected = core::mem::take(&mut self.selected);
+
+        if self.confirm_selected && (selected || matches!(input_state, InputState::InProgress(_))) {
+            self.held = self.held.saturating_add(1);
+        } else {
+            self.held = 0;
+        }
+    }
+
+    pub fn draw<C, D>(
+        &self,
+        confirmation: &Confirmation,
+        theme: &C,
+        font: &'static MonoFont<'static>,
+        display: &mut D,
+    ) -> Result<(), D::Error>
+    where
+        C: Theme,
+        D: DrawTarget<Color = C::Color>,
+    {
+        let labels = [confirmation.cancel_label(), confirmation.confirm_label()];
+
+        let label_width = labels
+            .iter()
+            .map(|label| label.chars().count())
+            .max()
+            .unwrap_or(0) as u32
+            * (font.character_size.width + font.character_spacing);
+        // Long labels are cut off to keep both buttons inside the frame.
+        let max_button_width = display
+            .bounding_box()
+            .size
+            .width
+            .saturating_sub(2 * MARGIN + 2 * (PADDING + 1) + PADDING)
+            / 2;
+        let button_size = Size::new(
+            (label_width + 2 * PADDING).min(max_button_width),
+            font.character_size.height + 2 * PADDING,
+        );
+        let footer = Size::new(
+            2 * button_size.width + PADDING,
+            button_size.height + PADDING,
+        );
+
+        let footer_area = draw_framed(confirmation.question(), footer, theme, font, display)?;
+        let buttons = Rectangle::with_center(
+            footer_area.center() + Point::new(0, PADDING as i32 / 2),
+            footer - Size::new(0, PADDING),
+        );
+
+        for (index, label) in labels.into_iter().enumerate() {
+            let confirm = index == 1;
+            let button = Rectangle::new(
+                buttons.top_left
+                    + Point::new(index as i32 * (button_size.width + PADDING) as i32, 0),
+                button_size,
+            );
+
+            let fill_width = match confirmation.hold_ticks() {
+                _ if confirm != self.confirm_selected => 0,
+                Some(ticks) if confirm => button_size.width * self.held.min(ticks) / ticks.max(1),
+                _ => button_size.width,
+            };
+
+            draw_button(label, button, fill_width, theme, font, display)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Draws a button, filled from the left up to `fill_width`.
+fn draw_button<C, D>(
+    label: &str,
+    button: Rectangle,
+    fill_width: u32,
+    theme: &C,
+    font: &'static MonoFont<'static>,
+    display: &mut D,
+) -> Result<(), D::Error>
+where
+    C: Theme,
+    D: DrawTarget<Color = C::Color>,
+{
+    let filled = Rectangle::new(button.top_left, Size::new(fill_width, button.size.height));
+    let empty = Rectangle::new(
+        button.top_left + Point::new(fill_width as i32, 0),
+        Size::new(button.size.width - fill_width, button.size.height),
+    );
+
+    button
+        .into_styled(PrimitiveStyle::with_stroke(theme.text_color(), 1))
+        .draw(display)?;
+    filled
+        .into_styled(PrimitiveStyle::with_fill(theme.text_color()))
+        .draw(display)?;
+
+    let label_position = button.center();
+    for (area, color) in [(filled, C::Color::default()), (empty, theme.text_color())] {
+        Text::with_text_style(
+            label,
+            label_position,
+            MonoTextStyle::new(font, color),
+            TextStyleBuilder::new()
+                .alignment(Alignment::Center)
+                .baseline(Baseline::Middle)
+                .build(),
+        )
+        .draw(&mut display.clipped(&area))?;
+    }
+
+    Ok(())
+}
+
+/// Draws `text` in a frame in the center of the display, and returns the area reserved below the
+/// text for `footer`.
+fn draw_framed<C, D>(
+    text: &str,
+    footer: Size,
+    theme: &C,
+    font: &'static MonoFont<'static>,
+    display: &mut D,
+) -> Result<Rectangle, D::Error>
+where
+    C: Theme,
+    D: DrawTarget<Color = C::Color>,
+{
+    let display_area = display.bounding_box();
+    let text_style = MonoTextStyle::new(font, theme.text_color());
+
+    // Frame and padding on both sides
+    let decoration = 2 * (PADDING + 1);
+    let max_text_width = display_area
+        .size
+        .width
+        .saturating_sub(2 * MARGIN + decoration);
+    let text_width = text_style
+        .measure_string(text, Point::zero(), Baseline::Top)
+        .bounding_box
+        .size
+        .width
+        .max(footer.width)
+        .min(max_text_width);
+
+    let text_box = TextBox::with_textbox_style(
+        text,
+        Rectangle::new(Point::zero(), Size::new(text_width, 0)),
+        text_style,
+        TextBoxStyleBuilder::new()
+            .alignment(HorizontalAlignment::Center)
+            .height_mode(HeightMode::FitToText)
+            .build(),
+    );
+
+    let frame = Rectangle::with_center(
+        display_area.center(),
+        text_box.bounds.size + Size::new(decoration, decoration + footer.height),
+    );
+
+    frame
+        .into_styled(
+            PrimitiveStyleBuilder::new()
+                .fill_color(C::Color::default())
+                .stroke_color(theme.text_color())
+                .stroke_width(1)
+                .build(),
+        )
+        .draw(display)?;
+
+    let text_offset = (PADDING + 1) as i32;
+    let text_box = text_box.translate(frame.top_left + Point::new(text_offset, text_offset));
+    text_box.draw(&mut display.clipped(&frame))?;
+
+    Ok(Rectangle::new(
+        text_box.bounds.anchor_point(AnchorPoint::BottomLeft) + Point::new(0, 1),
+        Size::new(text_box.bounds.size.width, footer.height),
+    ))
+}
+
+#[cfg(test)]
+mod test {
+    use embedded_graphics::{
+        mock_display::MockDisplay, pixelcolor::BinaryColor, prelude::Point, Drawable,
+    };
+
+    use core::fmt::Write;
+
+    use crate::{
+        interaction::{Action, Interaction, Navigation},
+        Menu,
+    };
+
+    #[test]
+    fn overlays_capture_input() {
+        let mut menu = Menu::build("Title")
+            .add_item("First", (), |_| 0)
+            .add_item("Second", (), |_| 1)
+            .build();
+
+        let mut display = MockDisplay::<BinaryColor>::new();
+        display.set_allow_overdraw(true);
+
+        menu.show_toast("Saved", 2);
+        menu.update(&display);
+        assert!(menu.overlay().is_some());
+        menu.update(&display);
+        assert!(menu.overlay().is_none());
+
+        let limit = 10;
+        write!(menu.show_popup("Limit: "), "{limit}").unwrap();
+        assert_eq!(
+            menu.overlay().map(|overlay| overlay.text()),
+            Some("Limit: 10")
+        );
+        assert_eq!(menu.interact(Interaction::Action(Action::Select)), None);
+        assert!(menu.overlay().is_none());
+        assert_eq!(menu.interact(Interaction::Action(Action::Select)), Some(0));
+
+        menu.show_toast("Saved", 10);
+        menu.interact(Interaction::Navigation(Navigation::Next));
+        assert_eq!(menu.selected_value(), 0);
+
+        menu.show_toast("Saved", 10);
+        menu.update(&display);
+        menu.draw(&mut display).unwrap();
+
+        // The frame fits the 30px wide, 10px tall text with 3px of frame and padding on each side.
+        assert_eq!(display.get_pixel(Point::new(14, 24)), Some(BinaryColor::On));
+        assert_eq!(display.get_pixel(Point::new(49, 39)), Some(BinaryColor::On));
+        assert_eq!(
+            display.get_pixel(Point::new(15, 25)),
+            Some(BinaryColor::Off)
+        );
+    }
+}
@@ -1,12 +1,14 @@
 use crate::{
     adapters::color_map::BinaryColorDrawTargetExt,
     collection::MenuItemCollection,
+    header::HeaderStyle,
     interaction::{InputAdapterSource, InputState},
     margin::Insets,
     selection_indicator::style::IndicatorStyle,
     theme::Theme,
     MenuState, MenuStyle,
 };
+use core::fmt::{self, Debug, Formatter};
 use embedded_graphics::{
     prelude::{DrawTarget, DrawTargetExt, Point, Size},
     primitives::Rectangle,
@@ -24,7 +26,7 @@ pub trait SelectionIndicatorController: Copy {
     fn update(&self, state: &mut Self::State);
 }
 
-#[derive(Clone, Copy, Default)]
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq)]
 pub struct StaticState {
     y_offset: i32,
 }
@@ -53,7 +55,7 @@ pub struct AnimatedPosition {
     frames: i32,
 }
 
-#[derive(Clone, Copy, Default)]
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq)]
 pub struct AnimatedState {
     current: i32,
     target: i32,
@@ -131,6 +133,33 @@ where
 {
 }
 
+impl<P, S> PartialEq for State<P, S>
+where
+    P: SelectionIndicatorController,
+    P::State: PartialEq,
+    S: IndicatorStyle,
+    S::State: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.position == other.position && self.state == other.state
+    }
+}
+
+impl<P, S> Debug for State<P, S>
+where
+    P: SelectionIndicatorController,
+    P::State: Debug,
+    S: IndicatorStyle,
+    S::State: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("State")
+            .field("position", &self.position)
+            .field("state", &self.state)
+            .finish()
+    }
+}
+
 #[derive(Clone, Copy, Debug)]
 pub(crate) struct Indicator<P, S> {
     pub controller: P,
@@ -160,19 +189,23 @@ where
         self.style.update(&mut state.state, input_state);
     }
 
+    pub fn padding(&self, menuitem_height: i32, state: &State<P, S>) -> Insets {
+        self.style.padding(&state.state, menuitem_height)
+    }
+
     pub fn item_height(&self, menuitem_height: i32, state: &State<P, S>) -> i32 {
-        let indicator_insets = self.style.padding(&state.state, menuitem_height);
+        let indicator_insets = self.padding(menuitem_height, state);
         menuitem_height + indicator_insets.top + indicator_insets.bottom
     }
 
-    pub fn draw<R, D, IT, C>(
+    pub fn draw<R, D, IT, C, H>(
         &self,
         selected_height: i32,
         selected_offset: i32,
         input_state: InputState,
         mut display: D,
         items: &impl MenuItemCollection<R>,
-        style: &MenuStyle<S, IT, P, R, C>,
+        style: &MenuStyle<S, IT, P, R, C, H>,
         menu_state: &MenuState<IT::InputAdapter, P, S>,
     ) -> Result<(), D::Error>
     where
@@ -180,6 +213,7 @@ where
         IT: InputAdapterSource<R>,
         P: SelectionIndicatorController,
         C: Theme,
+        H: HeaderStyle,
         S: IndicatorStyle,
     {
         let display_size = display.bounding_box().size;
@@ -197,10 +231,24 @@ where
 
         // Draw the selection indicator
         let selected_item_height = (selected_height + padding_top + padding_bottom) as u32;
-        let selected_item_area = Rectangle::new(
-            Point::new(0, selected_offset),
-            Size::new(display_size.width, selected_item_height),
-        );
+        let selected_item_area = match items.selection_area_of(menu_state.selected) {
+            // Only highlight a part of the item, e.g. a button in a row of buttons, or an item
+            // in a grid
+            Some(area) => Rectangle::new(
+                Point::new(
+                    items.bounds_of(menu_state.selected).top_left.x + area.top_left.x,
+                    selected_offset + area.top_left.y,
+                ),
+                Size::new(
+                    area.size.width + (padding_left + padding_right) as u32,
+                    area.size.height + (padding_top + padding_bottom) as u32,
+                ),
+            ),
+            None => Rectangle::new(
+                Point::new(0, selected_offset),
+                Size::new(display_size.width, selected_item_height),
+            ),
+        };
 
         let selection_area = self.style.draw(
             &menu_state.indicator_state.state,
@@ -231,4 +279,60 @@ where
                 .translated(content_area.top_left - Point::new(0, menu_state.list_offset)),
         )
     }
+
+    /// Draws the items of a carousel, moved by `offset`, and highlights the selected item.
+    pub fn draw_carousel<R, D, IT, C, H>(
+        &self,
+        offset: Point,
+        input_state: InputState,
+        mut display: D,
+        items: &impl MenuItemCollection<R>,
+        style: &MenuStyle<S, IT, P, R, C, H>,
+        menu_state: &MenuState<IT::InputAdapter, P, S>,
+    ) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = C::Color>,
+        IT: InputAdapterSource<R>,
+        P: SelectionIndicatorController,
+        C: Theme,
+        H: HeaderStyle,
+        S: IndicatorStyle,
+    {
+        let selected = items.bounds_of(menu_state.selected);
+        let area = items
+            .selection_area_of(menu_state.selected)
+            .unwrap_or(Rectangle::new(Point::zero(), selected.size));
+
+        // The insets expand the selection indicator around the item.
+        let Insets {
+            left,
+            top,
+            right,
+            bottom,
+        } = self.style.padding(
+            &menu_state.indicator_state.state,
+            selected.size.height as i32,
+        );
+
+        let selected_item_area = Rectangle::new(
+            offset + selected.top_left + area.top_left - Point::new(left, top),
+            area.size + Size::new((left + right) as u32, (top + bottom) as u32),
+        );
+
+        let selection_area = self.style.draw(
+            &menu_state.indicator_state.state,
+            input_state,
+            &style.theme,
+            &mut display.cropped(&selected_item_area),
+        )?;
+
+        let mapping_area = selection_area.translate(selected_item_area.top_left);
+        let mut inverting = display.map_colors(
+            &mapping_area,
+            style.theme.text_color(),
+            style.theme.selected_text_color(),
+        );
+
+        items.draw_styled(&style.text_style(), &mut inverting.translated(offset))
+    }
 }
@@ -24,7 +24,7 @@ impl AnimatedTriangle {
     }
 }
 
-#[derive(Default, Clone, Copy)]
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq)]
 pub struct State {
     current: i32,
 }
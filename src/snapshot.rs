@@ -0,0 +1,627 @@
+//! Headless snapshot testing of menus.
+//!
+//! [`Snapshot`] renders menus into an in-memory display and collects the rendered frames as
+//! ASCII-art. The collected frames can be compared to a golden file using
+//! [`Snapshot::assert_matches`]. Color menus can also be compared to a PNG golden file using
+//! [`SnapshotDisplay::assert_matches_png`]. Golden files are only written if the
+//! `UPDATE_SNAPSHOTS` environment variable is set.
+//!
+//! ```no_run
+//! use embedded_graphics::prelude::Size;
+//! use embedded_menu::{
+//!     interaction::{Interaction, Navigation},
+//!     snapshot::Snapshot,
+//!     Menu,
+//! };
+//!
+//! let mut menu = Menu::build("Menu")
+//!     .add_item("Foo", false, |b| b)
+//!     .add_item("Bar", false, |b| b)
+//!     .build();
+//!
+//! let mut snapshot = Snapshot::new(Size::new(64, 32));
+//! snapshot.capture_menu("initial", &mut menu);
+//! snapshot.step(&mut menu, Interaction::Navigation(Navigation::Next));
+//! snapshot.assert_matches("snapshots/menu.txt");
+//! ```
+
+extern crate std;
+
+use std::{
+    fmt::{Debug, Write as _},
+    fs::{self, File},
+    io::{BufReader, BufWriter},
+    path::Path,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+
+use embedded_graphics::{
+    pixelcolor::{BinaryColor, Gray8, GrayColor, Rgb555, Rgb565, Rgb666, Rgb888, RgbColor},
+    prelude::{DrawTarget, OriginDimensions, PixelColor, Point, Size},
+    Drawable, Pixel,
+};
+use embedded_layout::view_group::ViewGroup;
+
+use crate::{
+    collection::MenuItemCollection,
+    event::MenuEvent,
+    header::HeaderStyle,
+    interaction::{InputAdapter, InputAdapterSource},
+    selection_indicator::{style::IndicatorStyle, SelectionIndicatorController},
+    storage::SettingsStorage,
+    theme::Theme,
+    Menu,
+};
+
+/// Colors that can be displayed in an ASCII-art snapshot.
+pub trait AsciiColor: PixelColor {
+    /// Returns the character that represents the color.
+    fn to_ascii(self) -> char;
+}
+
+impl AsciiColor for BinaryColor {
+    fn to_ascii(self) -> char {
+        match self {
+            BinaryColor::Off => '_',
+            BinaryColor::On => '#',
+        }
+    }
+}
+
+impl AsciiColor for Gray8 {
+    fn to_ascii(self) -> char {
+        const RAMP: &[u8] = b"_:-=+*%#";
+
+        RAMP[self.luma() as usize * RAMP.len() / 256] as char
+    }
+}
+
+macro_rules! rgb_ascii_color {
+    ($($color:ty),+) => {
+        $(
+            impl AsciiColor for $color {
+                fn to_ascii(self) -> char {
+                    Gray8::from(self).to_ascii()
+                }
+            }
+        )+
+    };
+}
+
+rgb_ascii_color!(Rgb555, Rgb565, Rgb666, Rgb888);
+
+/// An in-memory display.
+///
+/// Pixels that haven't been drawn since the last [`SnapshotDisplay::clear_pixels`] call are
+/// rendered as `.` in ASCII-art snapshots.
+pub struct SnapshotDisplay<C> {
+    size: Size,
+    pixels: Vec<Option<C>>,
+}
+
+impl<C> SnapshotDisplay<C>
+where
+    C: PixelColor,
+{
+    pub fn new(size: Size) -> Self {
+        Self {
+            size,
+            pixels: vec![None; (size.width * size.height) as usize],
+        }
+    }
+
+    /// Resets every pixel to the undrawn state.
+    pub fn clear_pixels(&mut self) {
+        self.pixels.fill(None);
+    }
+
+    /// Returns the color of the pixel at `point`, or `None` if it wasn't drawn.
+    pub fn pixel(&self, point: Point) -> Option<C> {
+        self.index(point).and_then(|index| self.pixels[index])
+    }
+
+    fn index(&self, point: Point) -> Option<usize> {
+        let x = u32::try_from(point.x).ok()?;
+        let y = u32::try_from(point.y).ok()?;
+
+        if x < self.size.width && y < self.size.height {
+            Some((y * self.size.width + x) as usize)
+        } else {
+            None
+        }
+    }
+
+    fn rows(&self) -> impl Iterator<Item = &[Option<C>]> {
+        self.pixels.chunks(self.size.width.max(1) as usize)
+    }
+
+    /// Renders the display contents as ASCII-art, one line per pixel row.
+    pub fn to_ascii(&self) -> String
+    where
+        C: AsciiColor,
+    {
+        let mut ascii = String::new();
+        for row in self.rows() {
+            ascii.extend(row.iter().map(|pixel| pixel.map_or('.', C::to_ascii)));
+            ascii.push('\n');
+        }
+        ascii
+    }
+
+    fn rgb_pixels(&self) -> Vec<Rgb888>
+    where
+        C: Into<Rgb888>,
+    {
+        self.pixels
+            .iter()
+            .map(|pixel| pixel.map_or(Rgb888::BLACK, Into::into))
+            .collect()
+    }
+
+    /// Saves the display contents as a PNG image. Undrawn pixels are saved as black.
+    pub fn save_png(&self, path: impl AsRef<Path>) -> Result<(), png::EncodingError>
+    where
+        C: Into<Rgb888>,
+    {
+        let data = self
+            .rgb_pixels()
+            .into_iter()
+            .flat_map(|color| [color.r(), color.g(), color.b()])
+            .collect::<Vec<_>>();
+
+        let file = File::create(path)?;
+        let mut encoder =
+            png::Encoder::new(BufWriter::new(file), self.size.width, self.size.height);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+
+        encoder.write_header()?.write_image_data(&data)
+    }
+
+    /// Compares the display contents to the PNG golden file at `path`. Undrawn pixels are
+    /// compared as black.
+    ///
+    /// The golden file is written instead if the `UPDATE_SNAPSHOTS` environment variable is set.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the golden file doesn't exist or isn't an 8-bit RGB image, or with a map of the
+    /// differing pixels if the display contents don't match it.
+    #[track_caller]
+    pub fn assert_matches_png(&self, path: impl AsRef<Path>)
+    where
+        C: Into<Rgb888>,
+    {
+        let path = path.as_ref();
+
+        if update_golden_file(path) {
+            self.save_png(path).unwrap();
+            return;
+        }
+
+        let Ok(file) = File::open(path) else {
+            panic!(
+                "Snapshot {} is missing. Set UPDATE_SNAPSHOTS=1 to create it.",
+                path.display()
+            );
+        };
+
+        let (size, expected) = match read_png(file) {
+            Ok(image) => image,
+            Err(error) => panic!("Snapshot {} can't be read: {error}", path.display()),
+        };
+
+        if let Some(diff) = pixel_diff(size, &expected, self.size, &self.rgb_pixels()) {
+            panic!(
+                "Snapshot {} does not match. Set UPDATE_SNAPSHOTS=1 to update it.\n{diff}",
+                path.display()
+            );
+        }
+    }
+}
+
+impl<C> OriginDimensions for SnapshotDisplay<C> {
+    fn size(&self) -> Size {
+        self.size
+    }
+}
+
+impl<C> DrawTarget for SnapshotDisplay<C>
+where
+    C: PixelColor,
+{
+    type Color = C;
+    type Error = core::convert::Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        for Pixel(point, color) in pixels {
+            if let Some(index) = self.index(point) {
+                self.pixels[index] = Some(color);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Collects rendered frames for comparison against a golden file.
+pub struct Snapshot<C> {
+    pub display: SnapshotDisplay<C>,
+    frames: String,
+}
+
+impl<C> Snapshot<C>
+where
+    C: AsciiColor,
+{
+    pub fn new(size: Size) -> Self {
+        Self {
+            display: SnapshotDisplay::new(size),
+            frames: String::new(),
+        }
+    }
+
+    /// Returns the frames captured so far.
+    pub fn frames(&self) -> &str {
+        &self.frames
+    }
+
+    /// Draws `drawable` on a cleared display and appends the result to the captured frames.
+    pub fn capture<D>(&mut self, label: &str, drawable: &D)
+    where
+        D: Drawable<Color = C>,
+    {
+        self.display.clear_pixels();
+        drawable.draw(&mut self.display).unwrap();
+
+        writeln!(self.frames, "--- {label} ---").unwrap();
+        self.frames.push_str(&self.display.to_ascii());
+    }
+
+    /// Updates the menu and captures the resulting frame.
+    pub fn capture_menu<T, IT, VG, R, P, S, TH, H, E, ST>(
+        &mut self,
+        label: &str,
+        menu: &mut Menu<T, IT, VG, R, P, S, TH, H, E, ST>,
+    ) where
+        T: AsRef<str>,
+        R: Clone,
+        IT: InputAdapterSource<R>,
+        VG: ViewGroup + MenuItemCollection<R>,
+        P: SelectionIndicatorController,
+        S: IndicatorStyle,
+        TH: Theme<Color = C>,
+        H: HeaderStyle,
+        E: FnMut(MenuEvent),
+        ST: SettingsStorage,
+    {
+        menu.update(&self.display);
+        self.capture(label, menu);
+    }
+
+    /// Passes `input` to the menu, then updates the menu and captures the resulting frame.
+    ///
+    /// The frame is labeled with the input and the value returned by the menu, if any.
+    pub fn step<T, IT, VG, R, P, S, TH, H, E, ST>(
+        &mut self,
+        menu: &mut Menu<T, IT, VG, R, P, S, TH, H, E, ST>,
+        input: <IT::InputAdapter as InputAdapter>::Input,
+    ) -> Option<R>
+    where
+        T: AsRef<str>,
+        IT: InputAdapterSource<R>,
+        <IT::InputAdapter as InputAdapter>::Input: Debug,
+        VG: ViewGroup + MenuItemCollection<R>,
+        P: SelectionIndicatorController,
+        S: IndicatorStyle,
+        TH: Theme<Color = C>,
+        H: HeaderStyle,
+        E: FnMut(MenuEvent),
+        ST: SettingsStorage,
+        R: Clone + Debug,
+    {
+        let mut label = std::format!("{input:?}");
+        let result = menu.interact(input);
+        if let Some(result) = result.as_ref() {
+            write!(label, " -> {result:?}").unwrap();
+        }
+
+        self.capture_menu(&label, menu);
+
+        result
+    }
+
+    /// Updates the menu `count` times, capturing every frame.
+    pub fn idle<T, IT, VG, R, P, S, TH, H, E, ST>(
+        &mut self,
+        menu: &mut Menu<T, IT, VG, R, P, S, TH, H, E, ST>,
+        count: usize,
+    ) where
+        T: AsRef<str>,
+        R: Clone,
+        IT: InputAdapterSource<R>,
+        VG: ViewGroup + MenuItemCollection<R>,
+        P: SelectionIndicatorController,
+        S: IndicatorStyle,
+        TH: Theme<Color = C>,
+        H: HeaderStyle,
+        E: FnMut(MenuEvent),
+        ST: SettingsStorage,
+    {
+        for _ in 0..count {
+            self.capture_menu("update", menu);
+        }
+    }
+
+    /// Compares the captured frames to the golden file at `path`.
+    ///
+    /// The golden file is written instead if the `UPDATE_SNAPSHOTS` environment variable is set.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the golden file doesn't exist, or with a line-by-line diff if the captured frames
+    /// don't match it.
+    #[track_caller]
+    pub fn assert_matches(&self, path: impl AsRef<Path>) {
+        let path = path.as_ref();
+
+        if update_golden_file(path) {
+            fs::write(path, &self.frames).unwrap();
+            return;
+        }
+
+        let Ok(expected) = fs::read_to_string(path) else {
+            panic!(
+                "Snapshot {} is missing. Set UPDATE_SNAPSHOTS=1 to create it.",
+                path.display()
+            );
+        };
+
+        if let Some(diff) = diff(&expected, &self.frames) {
+            panic!(
+                "Snapshot {} does not match. Set UPDATE_SNAPSHOTS=1 to update it.\n{diff}",
+                path.display()
+            );
+        }
+    }
+}
+
+/// Returns whether the golden file at `path` should be written instead of compared, and creates
+/// its directory if so.
+fn update_golden_file(path: &Path) -> bool {
+    if std::env::var_os("UPDATE_SNAPSHOTS").is_none() {
+        return false;
+    }
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).unwrap();
+    }
+    true
+}
+
+/// Decodes an 8-bit RGB PNG image, as written by [`SnapshotDisplay::save_png`].
+fn read_png(file: File) -> Result<(Size, Vec<Rgb888>), String> {
+    let mut reader = png::Decoder::new(BufReader::new(file))
+        .read_info()
+        .map_err(|error| error.to_string())?;
+
+    let mut data = vec![0; reader.output_buffer_size().unwrap_or(0)];
+    let info = reader
+        .next_frame(&mut data)
+        .map_err(|error| error.to_string())?;
+
+    if info.color_type != png::ColorType::Rgb || info.bit_depth != png::BitDepth::Eight {
+        return Err(std::format!(
+            "expected an 8-bit RGB image, found {:?} with {:?}",
+            info.color_type,
+            info.bit_depth
+        ));
+    }
+
+    let pixels = data[..info.buffer_size()]
+        .chunks_exact(3)
+        .map(|rgb| Rgb888::new(rgb[0], rgb[1], rgb[2]))
+        .collect();
+
+    Ok((Size::new(info.width, info.height), pixels))
+}
+
+/// Returns a map of the differing pixels, or `None` if the images are equal.
+///
+/// Differing pixels are marked with `X` in the map, matching pixels with `.`.
+fn pixel_diff(
+    expected_size: Size,
+    expected: &[Rgb888],
+    actual_size: Size,
+    actual: &[Rgb888],
+) -> Option<String> {
+    if expected_size != actual_size {
+        return Some(std::format!(
+            "size: expected {}x{}, actual {}x{}",
+            expected_size.width,
+            expected_size.height,
+            actual_size.width,
+            actual_size.height
+        ));
+    }
+
+    let hex = |color: Rgb888| std::format!("#{:02x}{:02x}{:02x}", color.r(), color.g(), color.b());
+    let width = expected_size.width.max(1) as usize;
+
+    let mut differences = expected
+        .iter()
+        .zip(actual)
+        .enumerate()
+        .filter(|(_, (expected, actual))| expected != actual);
+    let (first, (first_expected, first_actual)) = differences.next()?;
+    let count = 1 + differences.count();
+
+    let mut diff = String::new();
+    writeln!(
+        diff,
+        "{count} pixels differ, first at ({}, {}): expected {}, actual {}",
+        first % width,
+        first / width,
+        hex(*first_expected),
+        hex(*first_actual)
+    )
+    .unwrap();
+
+    for (expected, actual) in expected.chunks(width).zip(actual.chunks(width)) {
+        diff.extend(
+            expected
+                .iter()
+                .zip(actual)
+                .map(|(expected, actual)| if expected == actual { '.' } else { 'X' }),
+        );
+        diff.push('\n');
+    }
+
+    Some(diff)
+}
+
+/// Returns a readable description of the differing lines, or `None` if the strings are equal.
+fn diff(expected: &str, actual: &str) -> Option<String> {
+    if expected == actual {
+        return None;
+    }
+
+    let mut expected_lines = expected.lines();
+    let mut actual_lines = actual.lines();
+    let mut diff = String::new();
+
+    for line in 1.. {
+        match (expected_lines.next(), actual_lines.next()) {
+            (None, None) => break,
+            (expected, actual) if expected == actual => {}
+            (expected, actual) => {
+                let expected = expected.unwrap_or("<missing>");
+                let actual = actual.unwrap_or("<missing>");
+
+                let markers = expected
+                    .chars()
+                    .map(Some)
+                    .chain(core::iter::repeat(None))
+                    .zip(actual.chars().map(Some).chain(core::iter::repeat(None)))
+                    .take(expected.chars().count().max(actual.chars().count()))
+                    .map(|(e, a)| if e == a { ' ' } else { '^' })
+                    .collect::<String>();
+
+                writeln!(diff, "line {line}:").unwrap();
+                writeln!(diff, "  expected: {expected}").unwrap();
+                writeln!(diff, "  actual:   {actual}").unwrap();
+                writeln!(diff, "            {}", markers.trim_end()).unwrap();
+            }
+        }
+    }
+
+    Some(diff)
+}
+
+#[cfg(test)]
+mod test {
+    use embedded_graphics::{
+        pixelcolor::{BinaryColor, Rgb888},
+        prelude::{Point, Primitive, RgbColor, Size},
+        primitives::{PrimitiveStyle, Rectangle},
+    };
+
+    use crate::{
+        interaction::{Action, Interaction, Navigation},
+        snapshot::{diff, pixel_diff, Snapshot, SnapshotDisplay},
+        Menu,
+    };
+
+    #[test]
+    fn renders_ascii() {
+        let mut snapshot = Snapshot::<BinaryColor>::new(Size::new(4, 3));
+
+        snapshot.capture(
+            "rect",
+            &Rectangle::new(Point::new(1, 0), Size::new(2, 2))
+                .into_styled(PrimitiveStyle::with_fill(BinaryColor::On)),
+        );
+
+        assert_eq!(snapshot.frames(), "--- rect ---\n.##.\n.##.\n....\n");
+    }
+
+    #[test]
+    fn diff_marks_differences() {
+        assert_eq!(diff("a\nb\n", "a\nb\n"), None);
+        assert_eq!(
+            diff("..#.\n", "..##\n.\n").unwrap(),
+            "line 1:\n  expected: ..#.\n  actual:   ..##\n               ^\n\
+             line 2:\n  expected: <missing>\n  actual:   .\n            ^^^^^^^^^\n"
+        );
+    }
+
+    #[test]
+    fn menu_snapshot() {
+        let mut menu = Menu::build("Menu")
+            .add_item("Foo", false, |b| b)
+            .add_item("Bar", false, |b| b)
+            .build();
+
+        let mut snapshot = Snapshot::new(Size::new(48, 32));
+        snapshot.capture_menu("initial", &mut menu);
+        snapshot.step(&mut menu, Interaction::Navigation(Navigation::Next));
+        assert_eq!(
+            snapshot.step(&mut menu, Interaction::Action(Action::Select)),
+            Some(true)
+        );
+        snapshot.idle(&mut menu, 1);
+
+        snapshot.assert_matches(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/snapshots/menu_snapshot.txt"
+        ));
+    }
+
+    #[test]
+    fn png_snapshot() {
+        let mut display = SnapshotDisplay::<Rgb888>::new(Size::new(3, 2));
+        embedded_graphics::Drawable::draw(
+            &Rectangle::new(Point::zero(), Size::new(1, 1))
+                .into_styled(PrimitiveStyle::with_fill(Rgb888::RED)),
+            &mut display,
+        )
+        .unwrap();
+
+        // Concurrent test runs must not write the same file
+        let path = std::env::temp_dir().join(std::format!(
+            "embedded-menu-png-export-{}.png",
+            std::process::id()
+        ));
+        display.save_png(&path).unwrap();
+
+        let bytes = std::fs::read(&path).unwrap();
+        assert_eq!(&bytes[1..4], b"PNG");
+
+        display.assert_matches_png(&path);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn pixel_diff_marks_differences() {
+        let size = Size::new(3, 2);
+        let expected = [Rgb888::BLACK; 6];
+        let mut actual = expected;
+        assert_eq!(pixel_diff(size, &expected, size, &actual), None);
+
+        actual[1] = Rgb888::RED;
+        actual[5] = Rgb888::WHITE;
+        assert_eq!(
+            pixel_diff(size, &expected, size, &actual).unwrap(),
+            "2 pixels differ, first at (1, 0): expected #000000, actual #ff0000\n.X.\n..X\n"
+        );
+        assert_eq!(
+            pixel_diff(size, &expected, Size::new(2, 3), &actual).unwrap(),
+            "size: expected 3x2, actual 2x3"
+        );
+    }
+}
@@ -0,0 +1,246 @@
+//! Persistent storage of menu item values.
+//!
+//! Menu items can be bound to a storage key using
+//! [`MenuItem::with_storage_key`](crate::items::MenuItem::with_storage_key). A menu built with
+//! [`MenuBuilder::with_storage`](crate::builder::MenuBuilder::with_storage) loads the values of
+//! bound items from the storage, and writes them back whenever the user changes them.
+//!
+//! Values can also be transferred explicitly, using
+//! [`MenuBuilder::load_settings`](crate::builder::MenuBuilder::load_settings) and
+//! [`Menu::store_settings`](crate::Menu::store_settings).
+//!
+//! Values are stored as `u32` numbers, see [`SelectValue::stored_value`].
+//!
+//! To reduce flash wear, values can be collected in a [`MemoryStorage`], which tracks changes and
+//! only writes changed values to the backing storage when [`MemoryStorage::commit`] is called.
+//!
+//! [`SelectValue::stored_value`]: crate::items::menu_item::SelectValue::stored_value
+
+/// Key-value storage for menu item values.
+pub trait SettingsStorage {
+    /// Returns the value stored for `key`, if any.
+    fn get(&self, key: &str) -> Option<u32>;
+
+    /// Stores `value` for `key`.
+    ///
+    /// Returns `false` if the value couldn't be stored, e.g. because the storage is full.
+    fn set(&mut self, key: &'static str, value: u32) -> bool;
+}
+
+/// The unit type is used by menus that don't have a storage. It discards every value.
+impl SettingsStorage for () {
+    fn get(&self, _key: &str) -> Option<u32> {
+        None
+    }
+
+    fn set(&mut self, _key: &'static str, _value: u32) -> bool {
+        true
+    }
+}
+
+impl<S> SettingsStorage for &mut S
+where
+    S: SettingsStorage + ?Sized,
+{
+    fn get(&self, key: &str) -> Option<u32> {
+        (**self).get(key)
+    }
+
+    fn set(&mut self, key: &'static str, value: u32) -> bool {
+        (**self).set(key, value)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Entry {
+    key: &'static str,
+    value: u32,
+    dirty: bool,
+}
+
+/// Fixed capacity, in-memory settings storage.
+///
+/// Entries that are changed are marked dirty until they are committed to a backing storage.
+/// Setting a new key when the storage is full fails.
+#[derive(Clone, Copy, Debug)]
+pub struct MemoryStorage<const N: usize> {
+    entries: [Option<Entry>; N],
+}
+
+impl<const N: usize> Default for MemoryStorage<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> MemoryStorage<N> {
+    pub const fn new() -> Self {
+        Self { entries: [None; N] }
+    }
+
+    /// Loads every value of `storage` that belongs to one of the `keys`, without marking them
+    /// dirty.
+    pub fn load_from(&mut self, storage: &impl SettingsStorage, keys: &[&'static str]) {
+        for &key in keys {
+            if let Some(value) = storage.get(key) {
+                self.set(key, value);
+                if let Some(entry) = self.entry_mut(key) {
+                    entry.dirty = false;
+                }
+            }
+        }
+    }
+
+    /// Returns whether any value has changed since the last commit.
+    pub fn is_dirty(&self) -> bool {
+        self.entries.iter().flatten().any(|entry| entry.dirty)
+    }
+
+    /// Writes the changed values into `storage` and marks them clean.
+    ///
+    /// Values that `storage` fails to store stay dirty. Returns `false` if there were any.
+    pub fn commit(&mut self, storage: &mut impl SettingsStorage) -> bool {
+        let mut stored = true;
+        for entry in self.entries.iter_mut().flatten() {
+            if entry.dirty {
+                if storage.set(entry.key, entry.value) {
+                    entry.dirty = false;
+                } else {
+                    stored = false;
+                }
+            }
+        }
+        stored
+    }
+
+    fn entry_mut(&mut self, key: &str) -> Option<&mut Entry> {
+        self.entries
+            .iter_mut()
+            .flatten()
+            .find(|entry| entry.key == key)
+    }
+}
+
+impl<const N: usize> SettingsStorage for MemoryStorage<N> {
+    fn get(&self, key: &str) -> Option<u32> {
+        self.entries
+            .iter()
+            .flatten()
+            .find(|entry| entry.key == key)
+            .map(|entry| entry.value)
+    }
+
+    fn set(&mut self, key: &'static str, value: u32) -> bool {
+        if let Some(entry) = self.entry_mut(key) {
+            if entry.value != value {
+                entry.value = value;
+                entry.dirty = true;
+            }
+        } else if let Some(free) = self.entries.iter_mut().find(|entry| entry.is_none()) {
+            *free = Some(Entry {
+                key,
+                value,
+                dirty: true,
+            });
+        } else {
+            return false;
+        }
+
+        true
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::vec::Vec;
+
+    use crate::{
+        event::MenuEvent,
+        interaction::{Action, Interaction, Navigation},
+        items::MenuItem,
+        storage::{MemoryStorage, SettingsStorage},
+        Menu,
+    };
+
+    #[test]
+    fn changes_are_committed() {
+        let mut flash = MemoryStorage::<4>::new();
+        flash.set("a", 1);
+        flash.set("b", 2);
+
+        let mut cache = MemoryStorage::<2>::new();
+        cache.load_from(&flash, &["a", "b"]);
+        assert!(!cache.is_dirty());
+
+        cache.set("a", 1);
+        assert!(!cache.is_dirty());
+
+        assert!(cache.set("b", 3));
+        assert!(!cache.set("c", 4));
+        assert!(cache.is_dirty());
+        assert_eq!(cache.get("c"), None);
+
+        let mut target = MemoryStorage::<4>::new();
+        assert!(cache.commit(&mut target));
+        assert!(!cache.is_dirty());
+        assert_eq!(target.get("a"), None);
+        assert_eq!(target.get("b"), Some(3));
+
+        let mut full = MemoryStorage::<0>::new();
+        cache.set("a", 5);
+        assert!(!cache.commit(&mut full));
+        assert!(cache.is_dirty());
+    }
+
+    #[test]
+    fn settings_are_loaded_and_stored() {
+        let mut storage = MemoryStorage::<1>::new();
+        storage.set("second", 1);
+        storage.commit(&mut MemoryStorage::<1>::new());
+
+        let mut events = Vec::new();
+        let mut menu = Menu::build("Title")
+            .add_menu_item(
+                MenuItem::new("First", false)
+                    .with_value_converter(|v| (0, v))
+                    .with_storage_key("first"),
+            )
+            .add_menu_item(
+                MenuItem::new("Second", false)
+                    .with_value_converter(|v| (1, v))
+                    .with_storage_key("second"),
+            )
+            .add_item("Unbound", false, |v| (2, v))
+            .add_menu_item(
+                MenuItem::new("Third", false)
+                    .with_value_converter(|v| (3, v))
+                    .with_storage_key("third"),
+            )
+            .with_event_handler(|event| events.push(event))
+            .with_storage(&mut storage)
+            .build();
+
+        assert_eq!(menu.selected_value(), (0, false));
+        menu.interact(Interaction::Navigation(Navigation::Next));
+        assert_eq!(menu.selected_value(), (1, true));
+
+        let mut flash = MemoryStorage::<4>::new();
+        assert!(!menu.storage().is_dirty());
+
+        menu.interact(Interaction::Action(Action::Select));
+        assert!(menu.storage().is_dirty());
+        assert!(menu.storage_mut().commit(&mut flash));
+        assert_eq!(flash.get("first"), None);
+        assert_eq!(flash.get("second"), Some(0));
+
+        // The storage has no room for a third key.
+        menu.interact(Interaction::Navigation(Navigation::End));
+        menu.interact(Interaction::Action(Action::Select));
+        assert_eq!(menu.storage().get("third"), None);
+
+        let mut full = MemoryStorage::<1>::new();
+        assert!(!menu.store_settings(&mut full));
+
+        assert_eq!(events.last(), Some(&MenuEvent::StoreFailed { index: 3 }));
+    }
+}
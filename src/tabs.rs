@@ -0,0 +1,483 @@
+//! Tabs that split the items of a menu into pages.
+//!
+//! See [`MenuBuilder::add_tab`](crate::builder::MenuBuilder::add_tab).
+
+use core::{any::Any, marker::PhantomData, ops::Range};
+
+use embedded_graphics::{
+    draw_target::DrawTargetExt,
+    mono_font::MonoTextStyle,
+    pixelcolor::BinaryColor,
+    prelude::{DrawTarget, Point, Primitive, Size},
+    primitives::{PrimitiveStyle, Rectangle},
+    text::{renderer::TextRenderer, Baseline, Text},
+    Drawable,
+};
+use embedded_layout::View;
+
+use crate::{
+    collection::MenuItemCollection,
+    interaction::Direction,
+    items::{Conditions, Confirmation},
+    storage::SettingsStorage,
+};
+
+/// Space between the title of a tab and its edges.
+const TAB_PADDING: u32 = 2;
+
+/// A tab of a menu.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Tab {
+    title: &'static str,
+    /// The selected item and the list offset when the tab was last displayed.
+    saved: Option<(usize, i32)>,
+}
+
+impl Tab {
+    /// Returns the title of the tab.
+    pub fn title(&self) -> &'static str {
+        self.title
+    }
+}
+
+/// The first item of a tab, added with
+/// [`MenuBuilder::add_tab`](crate::builder::MenuBuilder::add_tab).
+///
+/// The tab is stored with its first item, so menus without tabs don't store any tab state, and
+/// every tab has at least one item.
+pub struct TabStart<I> {
+    tab: Tab,
+    item: I,
+}
+
+impl<I> TabStart<I> {
+    pub(crate) const fn new(title: &'static str, item: I) -> Self {
+        Self {
+            tab: Tab { title, saved: None },
+            item,
+        }
+    }
+}
+
+impl<I, R> MenuItemCollection<R> for TabStart<I>
+where
+    I: MenuItemCollection<R>,
+{
+    fn bounds_of(&self, nth: usize) -> Rectangle {
+        self.item.bounds_of(nth)
+    }
+
+    fn value_of(&self, nth: usize) -> R {
+        self.item.value_of(nth)
+    }
+
+    fn interact_with(&mut self, nth: usize) -> R {
+        self.item.interact_with(nth)
+    }
+
+    fn interact_changed(&mut self, nth: usize) -> (R, bool) {
+        self.item.interact_changed(nth)
+    }
+
+    fn step(&mut self, nth: usize, direction: Direction) -> Option<R> {
+        self.item.step(nth, direction)
+    }
+
+    fn selectable(&self, nth: usize) -> bool {
+        self.item.selectable(nth)
+    }
+
+    fn count(&self) -> usize {
+        self.item.count()
+    }
+
+    fn title_of(&self, nth: usize) -> &str {
+        self.item.title_of(nth)
+    }
+
+    fn filter(&mut self, filter: &str) {
+        self.item.filter(filter);
+    }
+
+    fn id_of(&self, nth: usize) -> Option<u16> {
+        self.item.id_of(nth)
+    }
+
+    fn index_of(&self, id: u16) -> Option<usize> {
+        self.item.index_of(id)
+    }
+
+    fn load_settings(&mut self, storage: &dyn SettingsStorage) {
+        self.item.load_settings(storage);
+    }
+
+    fn store_settings(&self, storage: &mut dyn SettingsStorage) -> bool {
+        self.item.store_settings(storage)
+    }
+
+    fn store_setting_of(&self, nth: usize, storage: &mut dyn SettingsStorage) -> bool {
+        self.item.store_setting_of(nth, storage)
+    }
+
+    fn stored_value_of(&self, nth: usize) -> Option<u32> {
+        self.item.stored_value_of(nth)
+    }
+
+    fn set_stored_value(&mut self, nth: usize, value: u32) -> bool {
+        self.item.set_stored_value(nth, value)
+    }
+
+    fn set_style_of(&mut self, nth: usize, text_style: &MonoTextStyle<'_, BinaryColor>) {
+        self.item.set_style_of(nth, text_style);
+    }
+
+    fn set_visible(&mut self, nth: usize, visible: bool) -> bool {
+        self.item.set_visible(nth, visible)
+    }
+
+    fn set_enabled(&mut self, nth: usize, enabled: bool) -> bool {
+        self.item.set_enabled(nth, enabled)
+    }
+
+    fn any_value_of(&self, nth: usize) -> Option<&dyn Any> {
+        self.item.any_value_of(nth)
+    }
+
+    fn set_any_value_of(&mut self, nth: usize, value: &dyn Any) -> bool {
+        self.item.set_any_value_of(nth, value)
+    }
+
+    fn conditions_of(&self, nth: usize) -> Conditions {
+        self.item.conditions_of(nth)
+    }
+
+    fn confirmation_of(&self, nth: usize) -> Option<Confirmation> {
+        self.item.confirmation_of(nth)
+    }
+
+    fn selection_area_of(&self, nth: usize) -> Option<Rectangle> {
+        self.item.selection_area_of(nth)
+    }
+
+    fn help_of(&self, nth: usize) -> Option<&str> {
+        self.item.help_of(nth)
+    }
+
+    fn translate_item(&mut self, nth: usize, by: Point) {
+        self.item.translate_item(nth, by);
+    }
+
+    fn tab_count(&self) -> usize {
+        1
+    }
+
+    fn tab(&self, nth: usize) -> Option<(usize, &Tab)> {
+        (nth == 0).then_some((0, &self.tab))
+    }
+
+    fn tab_mut(&mut self, nth: usize) -> Option<&mut Tab> {
+        (nth == 0).then_some(&mut self.tab)
+    }
+
+    fn draw_styled<D>(
+        &self,
+        text_style: &MonoTextStyle<'static, BinaryColor>,
+        display: &mut D,
+    ) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = BinaryColor>,
+    {
+        self.item.draw_styled(text_style, display)
+    }
+}
+
+impl<I> View for TabStart<I>
+where
+    I: View,
+{
+    fn translate_impl(&mut self, by: Point) {
+        self.item.translate_impl(by);
+    }
+
+    fn bounds(&self) -> Rectangle {
+        self.item.bounds()
+    }
+}
+
+/// Returns the index of the first item of the `nth` tab.
+fn start_of<R>(items: &impl MenuItemCollection<R>, nth: usize) -> Option<usize> {
+    items.tab(nth).map(|(start, _)| start)
+}
+
+/// Returns the indices of the items in the `nth` tab. Items added before the first tab belong to
+/// the first tab.
+pub(crate) fn range<R>(items: &impl MenuItemCollection<R>, nth: usize) -> Range<usize> {
+    let count = items.count();
+    let start = if nth == 0 {
+        0
+    } else {
+        start_of(items, nth).unwrap_or(count)
+    };
+    let end = start_of(items, nth + 1).unwrap_or(count);
+
+    start..end.max(start)
+}
+
+/// Returns whether the `item`th item is in the `current` tab. Every item is displayed in a menu
+/// without tabs.
+pub(crate) fn displays<R>(items: &impl MenuItemCollection<R>, current: usize, item: usize) -> bool {
+    items.tab_count() == 0 || range(items, current).contains(&item)
+}
+
+/// Returns the tab that contains the `item`th item.
+pub(crate) fn tab_of<R>(items: &impl MenuItemCollection<R>, item: usize) -> usize {
+    (1..items.tab_count())
+        .rev()
+        .find(|&nth| start_of(items, nth).is_some_and(|start| start <= item))
+        .unwrap_or(0)
+}
+
+/// Saves the selection and list offset of the `old` tab.
+///
+/// Returns the selection and list offset of the `new` tab, if it was displayed before.
+pub(crate) fn switch<R>(
+    items: &mut impl MenuItemCollection<R>,
+    old: usize,
+    new: usize,
+    selected: usize,
+    list_offset: i32,
+) -> Option<(usize, i32)> {
+    if let Some(tab) = items.tab_mut(old) {
+        tab.saved = Some((selected, list_offset));
+    }
+
+    items.tab(new).and_then(|(_, tab)| tab.saved)
+}
+
+/// Returns the vertical position and the height of the items in the `current` tab.
+pub(crate) fn extent<R>(items: &impl MenuItemCollection<R>, current: usize) -> (i32, i32) {
+    let range = range(items, current);
+    if range.is_empty() {
+        return (0, 0);
+    }
+
+    let top = items.bounds_of(range.start).top_left.y;
+    let bottom = range
+        .map(|nth| {
+            let bounds = items.bounds_of(nth);
+            bounds.top_left.y + bounds.size.height as i32
+        })
+        .max()
+        .unwrap_or(top);
+
+    (top, bottom - top)
+}
+
+/// Object-safe access to the tabs of a menu, used to draw the tab bar.
+pub(crate) trait TabBar {
+    fn count(&self) -> usize;
+    fn title(&self, nth: usize) -> &'static str;
+    /// Returns the displayed tab.
+    fn current(&self) -> usize;
+}
+
+/// Erases the return type of a [`MenuItemCollection`].
+pub(crate) struct MenuTabs<'a, C, R> {
+    items: &'a C,
+    current: usize,
+    _marker: PhantomData<R>,
+}
+
+impl<'a, C, R> MenuTabs<'a, C, R> {
+    pub const fn new(items: &'a C, current: usize) -> Self {
+        Self {
+            items,
+            current,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<C, R> TabBar for MenuTabs<'_, C, R>
+where
+    C: MenuItemCollection<R>,
+{
+    fn count(&self) -> usize {
+        self.items.tab_count()
+    }
+
+    fn title(&self, nth: usize) -> &'static str {
+        self.items.tab(nth).map_or("", |(_, tab)| tab.title)
+    }
+
+    fn current(&self) -> usize {
+        self.current
+    }
+}
+
+/// Draws the titles of the tabs into `area`, highlighting the displayed tab.
+///
+/// The tab bar scrolls horizontally to keep the displayed tab visible.
+pub(crate) fn draw_tab_bar<D>(
+    tabs: &dyn TabBar,
+    area: Rectangle,
+    text_style: MonoTextStyle<'static, D::Color>,
+    background: D::Color,
+    display: &mut D,
+) -> Result<(), D::Error>
+where
+    D: DrawTarget,
+{
+    let tab_width = |nth| {
+        text_style
+            .measure_string(tabs.title(nth), Point::zero(), Baseline::Top)
+            .bounding_box
+            .size
+            .width
+            + 2 * TAB_PADDING
+    };
+
+    // Scroll the tab bar if the current tab doesn't fit
+    let current_right: u32 = (0..=tabs.current()).map(tab_width).sum();
+    let scroll = current_right.saturating_sub(area.size.width) as i32;
+
+    let mut display = display.clipped(&area);
+    let mut left = area.top_left.x - scroll;
+
+    for nth in 0..tabs.count() {
+        let width = tab_width(nth);
+        let tab = Rectangle::new(
+            Point::new(left, area.top_left.y),
+            Size::new(width, text_style.font.character_size.height),
+        );
+
+        let mut tab_style = text_style;
+        if nth == tabs.current() {
+            if let Some(color) = text_style.text_color {
+                tab.into_styled(PrimitiveStyle::with_fill(color))
+                    .draw(&mut display)?;
+            }
+            tab_style.text_color = Some(background);
+        }
+
+        Text::with_baseline(
+            tabs.title(nth),
+            tab.top_left + Point::new(TAB_PADDING as i32, 0),
+            tab_style,
+            Baseline::Top,
+        )
+        .draw(&mut display)?;
+
+        left += width as i32;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use embedded_graphics::{
+        mock_display::MockDisplay, pixelcolor::BinaryColor, prelude::Point, Drawable,
+    };
+
+    use crate::{
+        interaction::{Interaction, Navigation},
+        items::MenuItem,
+        Menu, MenuLayout, MenuStyle,
+    };
+
+    #[test]
+    fn tabs_remember_their_selection() {
+        let mut menu = Menu::with_style("Title", MenuStyle::default())
+            .add_tab(
+                "Net",
+                MenuItem::new("WiFi", false).with_value_converter(|_| 0),
+            )
+            .add_item("IP", false, |_| 1)
+            .add_tab(
+                "Sys",
+                MenuItem::new("About", ())
+                    .with_value_converter(|_| unreachable!())
+                    .selectable::<false>(),
+            )
+            .add_item("Reset", false, |_| 3)
+            .add_item("Log", false, |_| 4)
+            .build();
+
+        let mut navigate = |navigation| {
+            menu.interact(Interaction::Navigation(navigation));
+            (menu.current_tab(), menu.state.selected)
+        };
+
+        // Navigation wraps around within the tab
+        assert_eq!(navigate(Navigation::Next), (0, 1));
+        assert_eq!(navigate(Navigation::Next), (0, 0));
+        assert_eq!(navigate(Navigation::Next), (0, 1));
+
+        // Left and right navigation is passed to the selected item
+        assert_eq!(navigate(Navigation::Right), (0, 1));
+        assert_eq!(navigate(Navigation::Left), (0, 1));
+
+        // The first selectable item is selected in a new tab
+        assert_eq!(navigate(Navigation::NextTab), (1, 3));
+        assert_eq!(navigate(Navigation::Next), (1, 4));
+        assert_eq!(navigate(Navigation::Next), (1, 3));
+        assert_eq!(navigate(Navigation::Previous), (1, 4));
+
+        assert_eq!(navigate(Navigation::PreviousTab), (0, 1));
+        assert_eq!(navigate(Navigation::NextTab), (1, 4));
+        assert_eq!(navigate(Navigation::NextTab), (0, 1));
+
+        menu.select_tab(1);
+        let display = MockDisplay::<BinaryColor>::new();
+        menu.update(&display);
+
+        // The tab is scrolled to its first item
+        assert_eq!(menu.state.list_offset, 18);
+        assert_eq!(menu.item_at(Point::new(5, 15)), Some(2));
+
+        let mut display = MockDisplay::<BinaryColor>::new();
+        display.set_allow_overdraw(true);
+        menu.draw(&mut display).unwrap();
+
+        // The current tab is highlighted in the header
+        assert_eq!(display.get_pixel(Point::new(1, 0)), None);
+        assert_eq!(display.get_pixel(Point::new(23, 0)), Some(BinaryColor::On));
+    }
+
+    #[test]
+    fn carousel_navigation_stays_in_the_tab() {
+        let mut menu = Menu::with_style(
+            "Title",
+            MenuStyle::default().with_layout(MenuLayout::Carousel {
+                spacing: 4,
+                page_dots: false,
+            }),
+        )
+        .add_tab(
+            "Net",
+            MenuItem::new("WiFi", false).with_value_converter(|_| 0),
+        )
+        .add_item("IP", false, |_| 1)
+        .add_tab(
+            "Sys",
+            MenuItem::new("Reset", false).with_value_converter(|_| 2),
+        )
+        .add_item("Log", false, |_| 3)
+        .build();
+
+        let mut navigate = |navigation| {
+            menu.interact(Interaction::Navigation(navigation));
+            (menu.current_tab(), menu.state.selected)
+        };
+
+        // Left and right move between the items of the tab
+        assert_eq!(navigate(Navigation::Left), (0, 1));
+        assert_eq!(navigate(Navigation::Right), (0, 0));
+
+        assert_eq!(navigate(Navigation::NextTab), (1, 2));
+        assert_eq!(navigate(Navigation::Left), (1, 3));
+        assert_eq!(navigate(Navigation::Right), (1, 2));
+        assert_eq!(navigate(Navigation::PreviousTab), (0, 0));
+    }
+}
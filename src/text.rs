@@ -0,0 +1,45 @@
+//! Fixed capacity text storage.
+
+use core::fmt::{self, Debug, Formatter, Write};
+
+/// Text of at most `N` bytes, stored in place.
+///
+/// Writing past the capacity fails and keeps the characters that fit.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) struct TextBuffer<const N: usize> {
+    buffer: [u8; N],
+    len: usize,
+}
+
+impl<const N: usize> TextBuffer<N> {
+    pub const fn new() -> Self {
+        Self {
+            buffer: [0; N],
+            len: 0,
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        core::str::from_utf8(&self.buffer[..self.len]).unwrap_or_default()
+    }
+}
+
+impl<const N: usize> Write for TextBuffer<N> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for c in s.chars() {
+            let end = self.len + c.len_utf8();
+            if end > N {
+                return Err(fmt::Error);
+            }
+            c.encode_utf8(&mut self.buffer[self.len..end]);
+            self.len = end;
+        }
+        Ok(())
+    }
+}
+
+impl<const N: usize> Debug for TextBuffer<N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Debug::fmt(self.as_str(), f)
+    }
+}